@@ -0,0 +1,173 @@
+use std::fmt;
+
+use crate::bigint::BigUint;
+
+// Answer is what a puzzle part gives back: usually a number, but some puzzles want text, or a picture drawn as a grid of characters
+// numbers are kept as numbers (rather than being turned straight into strings) so that they can be compared as numbers,
+// e.g. by verify, and so that anything bigger than an i64 is clearly marked as such
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Integer(i64),
+    // for anything that doesn't fit in an i64 (e.g. a u64 sum that's over i64::MAX), however big it is
+    // answers are never that big and negative, so this is only for non-negative numbers
+    BigInteger(BigUint),
+    Text(String),
+    // one string per row, without the newlines
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    // parse reads an answer back from how it's written down (e.g. in an answers file, or an example's .expected),
+    // picking the narrowest type it could be: a number if it's all digits, a grid if it has more than one line, and otherwise text
+    // a grid with only one row is written down just like text, so it comes back as Text: parse(grid.to_string()) is only the same
+    // grid when it has at least two rows
+    pub fn parse(s: &str) -> Answer {
+        if let Ok(v) = s.parse::<i64>() {
+            return Answer::Integer(v);
+        }
+        if let Ok(v) = s.parse::<BigUint>() {
+            return Answer::BigInteger(v);
+        }
+        if s.contains('\n') {
+            return Answer::Grid(s.lines().map(|row| row.to_string()).collect());
+        }
+        return Answer::Text(s.to_string());
+    }
+
+    // kind is the name of the answer's type, e.g. for the JSON output
+    pub fn kind(&self) -> &'static str {
+        return match self {
+            Answer::Integer(_) => "integer",
+            Answer::BigInteger(_) => "big_integer",
+            Answer::Text(_) => "text",
+            Answer::Grid(_) => "grid",
+        };
+    }
+}
+
+// answers are equal if they're the same type of thing with the same value, where numbers are compared by value whatever their size
+// (so Integer(3) == BigInteger(3), but Integer(3) != Text("3"))
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        return match (self, other) {
+            (Answer::Integer(a), Answer::Integer(b)) => a == b,
+            (Answer::BigInteger(a), Answer::BigInteger(b)) => a == b,
+            (Answer::Integer(a), Answer::BigInteger(b)) | (Answer::BigInteger(b), Answer::Integer(a)) => {
+                u64::try_from(*a).is_ok_and(|a| BigUint::from(a) == *b)
+            },
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (Answer::Grid(a), Answer::Grid(b)) => a == b,
+            _ => false,
+        };
+    }
+}
+
+impl Eq for Answer {}
+
+// the formatter is passed on (or padded with) rather than written to, so that e.g. {:>12} lines answers up like any other value
+// (apart from a grid, which is a picture over several lines, so there's nothing sensible to line up)
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Answer::Integer(v) => fmt::Display::fmt(v, f),
+            Answer::BigInteger(v) => fmt::Display::fmt(v, f),
+            Answer::Text(s) => f.pad(s),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        };
+    }
+}
+
+// numbers come out of the solvers in all sorts of types, so they can all be turned straight into an Answer with .into()
+// signed types up to 64 bits always fit in an Integer; anything else is only a BigInteger when it has to be
+macro_rules! answer_from_small_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(v: $t) -> Answer {
+                    return Answer::Integer(v as i64);
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! answer_from_big_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(v: $t) -> Answer {
+                    return match i64::try_from(v) {
+                        Ok(v) => Answer::Integer(v),
+                        Err(_) => Answer::BigInteger(BigUint::from(v)),
+                    };
+                }
+            }
+        )*
+    };
+}
+
+answer_from_small_int!(i8, i16, i32, i64, u8, u16, u32);
+answer_from_big_int!(u64, usize, u128);
+
+impl From<BigUint> for Answer {
+    fn from(v: BigUint) -> Answer {
+        return match v.to_u128().and_then(|v| i64::try_from(v).ok()) {
+            Some(v) => Answer::Integer(v),
+            None => Answer::BigInteger(v),
+        };
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        return Answer::Text(s);
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        return Answer::Text(s.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_uses_big_integers_when_it_has_to() {
+        assert_eq!(Answer::from(7_usize), Answer::Integer(7));
+        assert!(matches!(Answer::from(u64::MAX), Answer::BigInteger(v) if v == BigUint::from(u64::MAX)));
+        assert!(matches!(Answer::from(BigUint::from(12_u64)), Answer::Integer(12)));
+        assert!(matches!(Answer::from(-3_i32), Answer::Integer(-3)));
+    }
+
+    #[test]
+    fn compares_numbers_by_value_but_not_across_types() {
+        assert_eq!(Answer::Integer(3), Answer::BigInteger(BigUint::from(3_u64)));
+        assert_ne!(Answer::Integer(-3), Answer::BigInteger(BigUint::from(3_u64)));
+        assert_ne!(Answer::Integer(3), Answer::Text("3".to_string()));
+        assert_ne!(Answer::Integer(3), Answer::Integer(4));
+    }
+
+    #[test]
+    fn parses_what_it_displays() {
+        for answer in [Answer::Integer(-12), Answer::from(1_u128 << 70), Answer::from("cba"), Answer::Grid(vec!["#.#".to_string(), ".#.".to_string()])] {
+            let parsed = Answer::parse(&answer.to_string());
+            assert_eq!(parsed, answer);
+            assert_eq!(parsed.kind(), answer.kind());
+        }
+        // there's no telling a one row grid from text once it's written down
+        assert_eq!(Answer::parse(&Answer::Grid(vec!["#.#".to_string()]).to_string()), Answer::from("#.#"));
+    }
+
+    #[test]
+    fn pads_to_the_width_asked_for() {
+        assert_eq!(format!("{:>6}|", Answer::Integer(-12)), "   -12|");
+        assert_eq!(format!("{:<6}|", Answer::from(42_u64)), "42    |");
+        assert_eq!(format!("{:>25}", Answer::from(1_u128 << 70)), format!("{:>25}", (1_u128 << 70).to_string()));
+        assert_eq!(format!("{:*^7}", Answer::from("cba")), "**cba**");
+        assert_eq!(format!("{:>4}", Answer::Grid(vec!["#".to_string(), ".".to_string()])), "#\n.");
+        assert_eq!(format!("{}", Answer::Integer(7)), "7");
+    }
+}
@@ -0,0 +1,133 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::minitoml::{self, Value};
+use crate::{Answer, Part, PuzzleError, Registration};
+
+// Answers are the known-good answers for a day, as recorded in answers/dayN.toml, e.g.
+//
+//     # day 1
+//     part1 = 1195
+//     part2 = "abc"
+//
+// either part can be left out if it hasn't been solved (or checked) yet
+// a bare value is a number, and a quoted one is text (or a grid, if it has more than one line), even if it's all digits
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Answers {
+    pub fn get(&self, part: Part) -> Option<&Answer> {
+        return match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        };
+    }
+
+    // parse reads an answers file, which is a (very small) subset of TOML (see minitoml)
+    pub fn parse(contents: &str) -> Result<Answers, PuzzleError> {
+        let mut answers = Answers::default();
+        for (line_number, key, value) in minitoml::parse(contents)? {
+            match key.as_str() {
+                "part1" => answers.part1 = Some(answer(value)),
+                "part2" => answers.part2 = Some(answer(value)),
+                other => return Err(PuzzleError::at(line_number, 1, format!("unknown key {other:?}"))),
+            }
+        }
+        return Ok(answers);
+    }
+}
+
+fn answer(value: Value) -> Answer {
+    return match value {
+        Value::Integer(digits) => Answer::parse(&digits),
+        Value::String(s) if s.contains('\n') => Answer::Grid(s.lines().map(|row| row.to_string()).collect()),
+        Value::String(s) => Answer::Text(s),
+    };
+}
+
+pub fn answers_path(answers_dir: &Path, day: u32) -> PathBuf {
+    return answers_dir.join(format!("day{day}.toml"));
+}
+
+// load reads the recorded answers for a day, or None if there's no file for it
+pub fn load(answers_dir: &Path, day: u32) -> io::Result<Option<Answers>> {
+    let path = answers_path(answers_dir, day);
+    let contents = match fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    return Answers::parse(&contents)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {e}", path.display())));
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    // the answer matched the recorded one
+    Pass,
+    // the answer didn't match the recorded one (which includes being a different type of answer, e.g. text rather than a number)
+    Fail { expected: Answer, actual: Answer },
+    // the puzzle couldn't produce an answer at all
+    Error(PuzzleError),
+    // there's no recorded answer to compare against
+    Missing { actual: Answer },
+}
+
+impl Outcome {
+    // is_regression is whether this outcome means something has broken
+    pub fn is_regression(&self) -> bool {
+        return matches!(self, Outcome::Fail { .. } | Outcome::Error(_));
+    }
+}
+
+// verify runs one part of a day and compares the answer against the recorded one
+pub fn verify(registration: &Registration, part: Part, input: &str, answers: &Answers) -> Outcome {
+    let actual = match registration.solve(part, input) {
+        Ok(a) => a,
+        Err(e) => return Outcome::Error(e),
+    };
+    return match answers.get(part) {
+        None => Outcome::Missing { actual },
+        Some(expected) if *expected == actual => Outcome::Pass,
+        Some(expected) => Outcome::Fail { expected: expected.clone(), actual },
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_strings_and_integers() {
+        let answers = Answers::parse("# day 1\npart1 = \"1195\"\n\npart2 = 6_770 # checked\n").unwrap();
+        assert_eq!(answers, Answers { part1: Some(Answer::Text("1195".to_string())), part2: Some(Answer::Integer(6770)) });
+        let answers = Answers::parse("part1 = 18446744073709551616\npart2 = \"#.\\n.#\"\n").unwrap();
+        assert_eq!(answers.part1.map(|a| a.kind()), Some("big_integer"));
+        assert_eq!(answers.part2, Some(Answer::Grid(vec!["#.".to_string(), ".#".to_string()])));
+    }
+
+    #[test]
+    fn compares_answers_by_type() {
+        let day0 = crate::lookup(0).unwrap();
+        let answers = Answers::parse("part1 = \"abc\"\npart2 = 123\n").unwrap();
+        assert_eq!(verify(day0, Part::One, "abc", &answers), Outcome::Pass);
+        // day 0 always answers with text, so even text that looks the same isn't the recorded number
+        assert_eq!(verify(day0, Part::Two, "321", &answers), Outcome::Fail { expected: Answer::Integer(123), actual: Answer::from("123") });
+        // but quoting it records it as text, which does match
+        let answers = Answers::parse("part2 = \"123\"\n").unwrap();
+        assert_eq!(verify(day0, Part::Two, "321", &answers), Outcome::Pass);
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert_eq!(Answers::parse("part1 = \"unterminated").unwrap_err().line, Some(1));
+        assert_eq!(Answers::parse("\npart3 = 1").unwrap_err().line, Some(2));
+        assert!(Answers::parse("part1 = abc").is_err());
+        assert!(Answers::parse("part1").is_err());
+    }
+}
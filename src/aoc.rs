@@ -1,21 +1,105 @@
-mod day0;
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
-pub use day0::Day0;
-pub use day1::Day1;
-pub use day2::Day2;
-pub use day3::Day3;
-pub use day4::Day4;
-pub use day5::Day5;
-pub use day6::Day6;
-pub use day7::Day7;
-pub use day8::Day8;
-pub use day9::Day9;
\ No newline at end of file
+use crate::{Answer, ParsedInput, Part, Puzzle, PuzzleError};
+
+// helpers shared by more than one day
+pub mod dial;
+pub mod grid;
+pub mod interval;
+pub mod repeated;
+#[cfg(test)]
+mod random;
+
+// Registration is a day's entry in the registry: which day it is, the puzzle's title, and the solver for it
+pub struct Registration {
+    pub day: u32,
+    pub title: &'static str,
+    pub puzzle: &'static (dyn Puzzle + Sync),
+}
+
+// Unparsed is the input of a puzzle that isn't a Solution, which is solved by handing each part the whole input
+struct Unparsed<'a> {
+    puzzle: &'static (dyn Puzzle + Sync),
+    input: &'a str,
+}
+
+impl ParsedInput for Unparsed<'_> {
+    fn solve(&self, part: Part) -> Result<Answer, PuzzleError> {
+        return crate::solve(self.puzzle, part, self.input);
+    }
+    fn parsed_once(&self) -> bool {
+        return false;
+    }
+}
+
+impl Registration {
+    // solve runs one part of this day, filling in which day and part any error came from
+    pub fn solve(&self, part: Part, input: &str) -> Result<Answer, PuzzleError> {
+        return crate::solve(self.puzzle, part, input).map_err(|e| e.in_part(self.day, part));
+    }
+
+    // parse gets the input ready to solve either part of this day: a Solution parses it here, once for both parts,
+    // and any other puzzle just holds on to the input, so that either way the parts can then be solved with solve_parsed
+    pub fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn ParsedInput + 'a>, PuzzleError> {
+        return match self.puzzle.parse(input) {
+            Some(parsed) => parsed.map_err(|e| e.in_day(self.day)),
+            None => Ok(Box::new(Unparsed { puzzle: self.puzzle, input })),
+        };
+    }
+
+    // solve_parsed solves one part of this day from an input that parse has got ready
+    pub fn solve_parsed(&self, parsed: &dyn ParsedInput, part: Part) -> Result<Answer, PuzzleError> {
+        return parsed.solve(part).map_err(|e| e.in_part(self.day, part));
+    }
+}
+
+// register! is how each dayN.rs adds itself to the registry, e.g. `register!(1, "Secret Entrance", Day1);`
+// it just defines that module's REGISTRATION, which days! below then picks up
+macro_rules! register {
+    ($day:literal, $title:literal, $puzzle:ident) => {
+        pub(super) const REGISTRATION: crate::aoc::Registration = crate::aoc::Registration {
+            day: $day,
+            title: $title,
+            puzzle: &$puzzle,
+        };
+    };
+}
+
+// days! declares every day's module, exports its solver, and collects its REGISTRATION into DAYS
+// adding a day is then a single line here (plus the register! in the day itself)
+macro_rules! days {
+    ($($module:ident::$puzzle:ident),* $(,)?) => {
+        $(
+            mod $module;
+            pub use $module::$puzzle;
+        )*
+
+        static DAYS: &[Registration] = &[$($module::REGISTRATION),*];
+    };
+}
+
+days! {
+    day0::Day0,
+    day1::Day1,
+    day2::Day2,
+    day3::Day3,
+    day4::Day4,
+    day5::Day5,
+    day6::Day6,
+    day7::Day7,
+    day8::Day8,
+    day9::Day9,
+}
+
+// registry lists every registered day, in day order
+pub fn registry() -> &'static [Registration] {
+    return DAYS;
+}
+
+// lookup finds the registration for a given day, if there is one
+pub fn lookup(day: u32) -> Option<&'static Registration> {
+    return DAYS.iter().find(|r| r.day == day);
+}
+
+// puzzle looks up the solver for a given day, if there is one
+pub fn puzzle(day: u32) -> Option<&'static (dyn Puzzle + Sync)> {
+    return lookup(day).map(|r| r.puzzle);
+}
@@ -1,13 +1,14 @@
-use crate::Puzzle;
+use crate::{Answer, Puzzle, PuzzleError};
 
 // Day0 is a puzzle implementation which does basic string operations as a test of the overall framework.
 pub struct Day0;
+register!(0, "Framework Test", Day0);
 
 impl Puzzle for Day0 {
-    fn part1(&self, input: &String) -> String {
-        return input.to_string();
+    fn part1(&self, input: &str) -> Result<Answer, PuzzleError> {
+        return Ok(Answer::from(input));
     }
-    fn part2(&self, input: &String) -> String {
-        return input.chars().rev().collect::<String>();
+    fn part2(&self, input: &str) -> Result<Answer, PuzzleError> {
+        return Ok(Answer::Text(input.chars().rev().collect()));
     }
 }
@@ -1,68 +1,166 @@
-use crate::Puzzle;
+use crate::{Answer, PuzzleError, Solution};
+use crate::arithmetic;
+use crate::aoc::dial::Dial;
+use crate::error::column_of;
+use crate::report::{csv_field, json_string};
 
 // Day1 implements day 1 of AoC 2025, as uploaded at https://adventofcode.com/2025/day/1. 
 pub struct Day1;
+register!(1, "Secret Entrance", Day1);
+
+// line_number is only used to say where any error was
+fn to_val(raw_line: &str, line_number: usize) -> Result<i64, PuzzleError> {
+    let line = raw_line.trim();
+    let column = column_of(raw_line, line);
 
-fn to_val(line: &str) -> i32 {
-    if line.len() == 0 {
-        return 0;
-    }
     // get the direction
-    let direction = match line.chars().nth(0) {
+    let direction = match line.chars().next() {
         Some('L') => -1,
         Some('R') => 1,
-        default => panic!("invalid direction {:?}", default)
+        default => return Err(PuzzleError::at(line_number, column, format!("invalid direction {:?}", default))),
     };
     
-    // convert to an i32 and multiply by the direction
-    return match line[1..].parse::<i32>() {
-        Ok(v) => v * direction,
-        Err(_) => panic!("invalid line {}", line),
+    // convert to an i64 and multiply by the direction
+    // (the direction is always a single ASCII character, so the value starts at byte 1)
+    return match line[1..].parse::<i64>() {
+        Ok(v) => Ok(v * direction),
+        Err(e) => Err(PuzzleError::at(line_number, column + 1, format!("invalid rotation {:?}: {e}", &line[1..]))),
     }
 }
 
-impl Puzzle for Day1 {
-    fn part1(&self, input: &String) -> String {
-        let mut count = 0;
-        let end_pos= input.split('\n') // convert to lines
-                    .map(|x| to_val(x.trim())) // convert each line into the corresponding offset
-                    .fold(50, |x, y| {
-                        let v = (x+y+100)%100;
-                        if v == 0 { count+= 1;}
-                        return v;
-                    });
-        
-        println!("end_pos was {}", end_pos);
-
-        return count.to_string();
+// instructions are the lines with a rotation on, along with their line numbers
+// blank lines (like the one after the final newline) aren't rotations at all, rather than rotations of 0, which would count as
+// landing on 0 again if the dial was already there
+fn instructions(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    return input.split('\n')
+                .enumerate()
+                .map(|(i, line)| (i + 1, line))
+                .filter(|(_, line)| !line.trim().is_empty());
+}
+
+fn parse_input(input: &str) -> Result<Vec<i64>, PuzzleError> {
+    return instructions(input)
+                .map(|(line_number, line)| to_val(line, line_number)) // convert each line into the corresponding offset
+                .collect();
+}
+
+// the dial has 0 to 99 on it, and starts pointing at 50
+fn dial() -> Dial {
+    return Dial::new(100, 50);
+}
+
+// Step is one rotation in a trace of the dial's walk (see Day1::trace), e.g. for plotting it, or diffing it against another implementation to see where they part ways
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    // counting from 1
+    pub step: usize,
+    // the instruction as it was written, e.g. L68
+    pub instruction: String,
+    pub start: u64,
+    pub end: u64,
+    // how many times the dial pointed at 0 during the rotation (see dial::Crossings)
+    pub zeros: u64,
+}
+
+impl Step {
+    // to_json writes the step as a single line of JSON, e.g. {"step":1,"instruction":"L68","start":50,"end":82,"zeros":1}
+    pub fn to_json(&self) -> String {
+        return format!(
+            "{{\"step\":{},\"instruction\":{},\"start\":{},\"end\":{},\"zeros\":{}}}",
+            self.step, json_string(&self.instruction), self.start, self.end, self.zeros,
+        );
+    }
+
+    // to_csv writes the step as a single CSV row, matching Day1::TRACE_CSV_HEADER
+    pub fn to_csv(&self) -> String {
+        return format!("{},{},{},{},{}", self.step, csv_field(&self.instruction), self.start, self.end, self.zeros);
+    }
+}
+
+impl Day1 {
+    pub const TRACE_CSV_HEADER: &str = "step,instruction,start,end,zeros";
+
+    // trace is the dial's whole walk, a step per rotation, for `aoc trace 1`
+    pub fn trace(&self, input: &str) -> Result<Vec<Step>, PuzzleError> {
+        let mut dial = dial();
+        let mut steps = Vec::new();
+        for (line_number, line) in instructions(input) {
+            let rotation = to_val(line, line_number)?;
+            let start = dial.position();
+            let zeros = dial.rotate(rotation).zeros;
+            steps.push(Step { step: steps.len() + 1, instruction: line.trim().to_string(), start, end: dial.position(), zeros });
+        }
+        return Ok(steps);
     }
-    fn part2(&self, input: &String) -> String {
-        let mut count = 0;
-        let end_pos= input.split('\n') // convert to lines
-                    .map(|x| to_val(x.trim())) // convert each line into the corresponding offset
-                    .fold(50, |x, y| {
-                        // if the new value is a rotation of more than 100, then we can add those clicks already to reduce to the case where it's less than 100
-                        let hundred_change = (y / 100).abs();
-                        if hundred_change > 0 {
-                            count += hundred_change;
-                            println!("crossed 0 {} times in a move of {} from {} (total {})", hundred_change, y, x, count);
-                        }
-                        
-                        let offset = y % 100;
-
-                        if x != 0 && ((x + offset <= 0) || (x + offset >= 100)) {
-                            count += 1;
-                            println!("crossed 0 moving from {}: {} (total {})", x, y, count);
-                        }
-
-                        // and return the next value
-                        // I spent SO LONG on this being a buggy modulo where I was doing (x+y+100)%100 which doesn't work when y is greater than 100... 
-                        // I should have spotted this should have been offset (ended up debugging based on the endpos ending up negative for L200)
-                        return (x+offset+100)%100;
-                    });
-        
-        println!("end_pos was {}", end_pos);
-
-        return count.to_string();
+}
+
+impl Solution for Day1 {
+    // the rotations, as offsets (i.e. negative for left)
+    type Parsed<'a> = Vec<i64>;
+
+    fn parse(&self, input: &str) -> Result<Vec<i64>, PuzzleError> {
+        return parse_input(input);
+    }
+    fn part1(&self, rotations: &Vec<i64>) -> Result<Answer, PuzzleError> {
+        let mut dial = dial();
+        let count = rotations.iter().filter(|&&rotation| dial.rotate(rotation).ends_at_zero).count();
+
+        crate::debug!("end_pos was {}", dial.position());
+
+        return Ok(count.into());
+    }
+    fn part2(&self, rotations: &Vec<i64>) -> Result<Answer, PuzzleError> {
+        let mut dial = dial();
+        let mut count: u64 = 0;
+        for &rotation in rotations {
+            let start = dial.position();
+            let crossings = dial.rotate(rotation);
+            if crossings.zeros > 0 {
+                count = arithmetic::add(count, crossings.zeros, "the number of times the dial points at 0")?;
+                crate::trace!("crossed 0 {} times in a move of {} from {} (total {})", crossings.zeros, rotation, start, count);
+            }
+        }
+
+        crate::debug!("end_pos was {}", dial.position());
+
+        return Ok(count.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn big_rotations_stay_on_the_dial() {
+        // L250 from 50 ends at 0, after passing it twice on the way
+        let rotations = parse_input("L250\nR1").unwrap();
+        assert_eq!(Day1.part1(&rotations), Ok(Answer::Integer(1)));
+        assert_eq!(Day1.part2(&rotations), Ok(Answer::Integer(3)));
+    }
+
+    #[test]
+    fn blank_lines_arent_rotations() {
+        // L50 lands on 0, and the newlines after it mustn't count as landing there again
+        for input in ["L50\n", "L50\n\n\n"] {
+            let rotations = parse_input(input).unwrap();
+            assert_eq!(rotations, [-50]);
+            assert_eq!(Day1.part1(&rotations), Ok(Answer::Integer(1)));
+            assert_eq!(Day1.part2(&rotations), Ok(Answer::Integer(1)));
+            assert_eq!(Day1.trace(input).unwrap().iter().map(|step| step.zeros).sum::<u64>(), 1);
+        }
+    }
+
+    #[test]
+    fn trace_follows_the_dial() {
+        let steps = Day1.trace("L68\n\nR48\n").unwrap();
+        assert_eq!(steps, [
+            Step { step: 1, instruction: "L68".to_string(), start: 50, end: 82, zeros: 1 },
+            Step { step: 2, instruction: "R48".to_string(), start: 82, end: 30, zeros: 1 },
+        ]);
+        assert_eq!(steps[0].to_json(), "{\"step\":1,\"instruction\":\"L68\",\"start\":50,\"end\":82,\"zeros\":1}");
+        assert_eq!(steps[1].to_csv(), "2,R48,82,30,1");
+        // and it says where a bad instruction is, like parse does
+        assert_eq!(Day1.trace("L1\nX5").unwrap_err().line, Some(2));
     }
 }
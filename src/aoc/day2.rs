@@ -1,64 +1,115 @@
-use crate::Puzzle;
+use std::ops::RangeInclusive;
+
+use crate::{Answer, PuzzleError, Solution};
+use crate::arithmetic;
+use crate::aoc::repeated::{RepeatedBlocks, Repeats};
+use crate::error::position_of;
 
 // Day2 implements day 2 of AoC 2025, as uploaded at https://adventofcode.com/2025/day/2. 
 pub struct Day2;
+register!(2, "Gift Shop", Day2);
 
 // "any ID which is made only of some sequence of digits repeated twice"
 // i.e. any 2 digit multiple of 11, any 4 digit multiple of 101, any 6 digit multiple of 1001, any 8 digit multiple of 10001...
 // must be an even number of digits anyway
-// the brute force solution would be to list out all of the numbers and check if it's true for that value, but I think we can do it from just the start and end point of a range
-
-fn is_duplicate_number_under_factor(v: u64, factor: u32) -> bool {
-    // doubled numbers need to have an even number of digits
-    let number_of_digits = v.ilog10() + 1;
-    
-    if number_of_digits % factor != 0 {
-        return false;
-    }
-
-    // the number has an even number of digits, so now all we need to do is check that it's a multiple of 10*(1/2 digit count) + 1
-    let prop_count = number_of_digits / factor;
+// the brute force solution would be to list out all of the numbers and check if it's true for that value, but aoc::repeated works them out
+// from just the start and end point of a range; part 1 is IDs that are exactly 2 copies of a block, and part 2 is IDs that are any number of copies
+fn invalid_ids(part2: bool) -> RepeatedBlocks {
+    return match part2 {
+        false => RepeatedBlocks::new(10, Repeats::Exactly(2)),
+        true => RepeatedBlocks::new(10, Repeats::AtLeast(2)),
+    };
+}
 
-    let mut divisor = 1;
-    for _ in 1..factor {
-        divisor = divisor * 10_u64.pow(prop_count) + 1;
-    }
+// error_at is an error about token, which is a slice of input
+fn error_at(input: &str, token: &str, message: impl Into<String>) -> PuzzleError {
+    let (line, column) = position_of(input, token);
+    return PuzzleError::at(line, column, message);
+}
 
-    return (v % divisor) == 0;
+// parse_range parses one range like 11-22, which includes both ends, and is a slice of input (so that errors can say where it was)
+fn parse_range(input: &str, range: &str) -> Result<RangeInclusive<u64>, PuzzleError> {
+    let error = |token: &str, message: String| error_at(input, token, message);
 
+    let (start, end) = range.split_once('-').ok_or_else(|| error(range, format!("range {range:?} has no '-'")))?;
+    let parse = |v: &str| v.trim().parse::<u64>().map_err(|e| error(v, format!("invalid range bound {v:?}: {e}")));
+    let (start, end) = (parse(start)?, parse(end)?);
+    if end < start {
+        return Err(error(range, format!("range {range:?} ends before it starts")));
+    }
+    return Ok(start..=end);
 }
 
-fn is_duplicate_number(v: u64, part2: bool) -> bool {
-    if !part2 {
-        return is_duplicate_number_under_factor(v, 2);
+// parse_ranges parses the comma separated list of ranges, which can have whitespace (including newlines) around any of them
+fn parse_ranges(input: &str) -> Result<Vec<RangeInclusive<u64>>, PuzzleError> {
+    if input.trim().is_empty() {
+        return Err(PuzzleError::new("input has no ranges"));
     }
-
-    // otherwise, any factor of the digit count is a possibility
-    // to be lazy, can we just iterate over every possible number 
-    let number_of_digits = v.ilog10() + 1;
-    
-    return (2..=number_of_digits).any(|candidate| is_duplicate_number_under_factor(v, candidate));
+    // (a comma at the very end would leave an empty range after it, which is fine to ignore, but not one anywhere else)
+    let list = input.trim_end();
+    let list = list.strip_suffix(',').unwrap_or(list);
+    return list.split(',').map(|entry| match entry.trim() {
+        "" => Err(error_at(input, entry, "empty range between two commas")),
+        range => parse_range(input, range),
+    }).collect();
 }
 
-fn sum_vals_in_range(range: &str, part2: bool) -> u64 {
-    let (start, end) = range.split_once('-') .unwrap();
-    return ((start.parse::<u64>().unwrap())..(end.parse::<u64>().unwrap()))
-        .filter(|x: &u64| is_duplicate_number(*x, part2))
-        .sum();
+fn sum_all_ranges(ranges: &[RangeInclusive<u64>], part2: bool) -> Result<Answer, PuzzleError> {
+    let invalid_ids = invalid_ids(part2);
+    let mut total: u128 = 0;
+    for range in ranges {
+        total = arithmetic::add(total, invalid_ids.sum(range.clone())?, "the sum of all the ranges")?;
+    }
+    return Ok(total.into());
 }
 
-impl Puzzle for Day2 {
-    fn part1(&self, input: &String) -> String {
-        return input.split(',')
-                    .map(|x| sum_vals_in_range(x, false))
-                    .sum::<u64>()
-                    .to_string()
+impl Solution for Day2 {
+    type Parsed<'a> = Vec<RangeInclusive<u64>>;
+
+    fn parse(&self, input: &str) -> Result<Vec<RangeInclusive<u64>>, PuzzleError> {
+        return parse_ranges(input);
     }
-    fn part2(&self, input: &String) -> String {
-        return input.split(',')
-                    .map(|x| sum_vals_in_range(x, true))
-                    .sum::<u64>()
-                    .to_string()
+    fn part1(&self, ranges: &Vec<RangeInclusive<u64>>) -> Result<Answer, PuzzleError> {
+        return sum_all_ranges(ranges, false);
+    }
+    fn part2(&self, ranges: &Vec<RangeInclusive<u64>>) -> Result<Answer, PuzzleError> {
+        return sum_all_ranges(ranges, true);
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\
+1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
+824824821-824824827,2121212118-2121212124\n";
+
+    #[test]
+    fn ranges_include_both_ends() {
+        // 22 is the end of the first range, and was missed when ranges left out their end
+        let ranges = parse_ranges("11-22").unwrap();
+        assert_eq!(ranges, [11..=22]);
+        assert_eq!(sum_all_ranges(&ranges, false), Ok(Answer::Integer(33)));
+        assert_eq!((11..=22).filter(|&id| invalid_ids(false).is_repeated(id)).collect::<Vec<_>>(), [11, 22]);
+        assert_eq!(sum_all_ranges(&parse_ranges(EXAMPLE).unwrap(), false), Ok(Answer::Integer(1227775554)));
+        assert_eq!(sum_all_ranges(&parse_ranges(EXAMPLE).unwrap(), true), Ok(Answer::Integer(4174379265)));
+    }
+
+    #[test]
+    fn parser_tolerates_whitespace() {
+        assert_eq!(parse_ranges(" 11-22 ,\n95-115,\n").unwrap(), [11..=22, 95..=115]);
+        assert_eq!(parse_ranges("1 - 2").unwrap(), [1..=2]);
+    }
+
+    #[test]
+    fn parser_says_where_bad_ranges_are() {
+        let position = |input: &str| parse_ranges(input).map(|_| ()).map_err(|e| (e.line, e.column));
+        assert_eq!(position("11-22,95_115"), Err((Some(1), Some(7))));
+        assert_eq!(position("11-22,\n95-x"), Err((Some(2), Some(4))));
+        assert_eq!(position("11-22,\n 30-20"), Err((Some(2), Some(2))));
+        assert_eq!(position("11-22,,95-115"), Err((Some(1), Some(7))));
+        assert!(parse_ranges("\n").is_err());
+    }
+}
@@ -1,7 +1,9 @@
-use crate::Puzzle;
+use crate::{Answer, Puzzle, PuzzleError};
+use crate::arithmetic;
 
 // Day3 implements day 23 of AoC 2025, as uploaded at https://adventofcode.com/2025/day/3. 
 pub struct Day3;
+register!(3, "Lobby", Day3);
 
 fn add_val(vec: &mut Vec<u64>, value: u64, top_n: u32) {
     // if we don't already have the first n, add it
@@ -52,7 +54,8 @@ fn as_val(vec: &[u64]) -> u64 {
     return base;
 }
 
-fn bank_joltage(line: &str, top_n: u32) -> u64 {
+// line_number is only used to say where any error was
+fn bank_joltage(line: &str, line_number: usize, top_n: u32) -> Result<u64, PuzzleError> {
     // NB: this doesn't actually need to be a Vec, we could use a fixed-length
     // array of size top_n instead to avoid heap allocation 
     // I'm currently writing lots of Go where the only thing you get is the
@@ -61,29 +64,60 @@ fn bank_joltage(line: &str, top_n: u32) -> u64 {
     let mut top_n_vals: Vec<u64> = Vec::new();
     
     // convert to ints and try and add each to the top_n array
-    let char_count = line.chars()
-                                .map(|x| add_val(&mut top_n_vals, x.to_digit(10).unwrap().into(), top_n))
-                                .count();
+    let mut char_count = 0;
+    for (i, x) in line.chars().enumerate() {
+        let digit = x.to_digit(10)
+            .ok_or_else(|| PuzzleError::at(line_number, i + 1, format!("battery {x:?} is not a digit")))?;
+        add_val(&mut top_n_vals, digit.into(), top_n);
+        char_count += 1;
+    }
     
-    println!("processed a line with {char_count} elements");
-    println!("line {line} had top vals {top_n_vals:?}");
+    crate::trace!("processed a line with {char_count} elements");
+    crate::trace!("line {line} had top vals {top_n_vals:?}");
     
     // then return the value of the array 
-    return as_val(&top_n_vals);
+    return Ok(as_val(&top_n_vals));
+}
+
+fn total_joltage(input: &str, top_n: u32) -> Result<Answer, PuzzleError> {
+    let mut total = 0;
+    for (i, line) in input.split('\n').enumerate() {
+        total = arithmetic::add(total, bank_joltage(line.trim(), i + 1, top_n)?, "the total joltage")?;
+    }
+    return Ok(total.into());
 }
 
 impl Puzzle for Day3 {
-    fn part1(&self, input: &String) -> String {
-        return input.split('\n')
-                    .map(|line| bank_joltage(line.trim(), 2))
-                    .sum::<u64>()
-                    .to_string()
+    fn part1(&self, input: &str) -> Result<Answer, PuzzleError> {
+        return total_joltage(input, 2);
     }
-    fn part2(&self, input: &String) -> String {
-        return input.split('\n')
-                    .map(|line| bank_joltage(line.trim(), 12))
-                    .sum::<u64>()
-                    .to_string()
+    fn part2(&self, input: &str) -> Result<Answer, PuzzleError> {
+        return total_joltage(input, 12);
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_biggest_batteries_in_order() {
+        assert_eq!(bank_joltage("987654321111111", 1, 2), Ok(98));
+        assert_eq!(bank_joltage("811111111111119", 1, 2), Ok(89));
+        assert_eq!(bank_joltage("818181911112111", 1, 12), Ok(888911112111));
+        // a bank with no more batteries than are wanted turns them all on
+        assert_eq!(bank_joltage("12", 1, 12), Ok(12));
+    }
+
+    #[test]
+    fn says_where_a_battery_isnt_a_digit() {
+        let e = total_joltage("12345\n12x45\n", 2).unwrap_err();
+        assert_eq!((e.line, e.column), (Some(2), Some(3)));
+    }
+
+    #[test]
+    fn blank_lines_add_nothing() {
+        assert_eq!(total_joltage("91\n", 2), total_joltage("91", 2));
+        assert_eq!(total_joltage("", 2), Ok(Answer::from(0)));
+    }
+}
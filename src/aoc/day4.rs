@@ -1,83 +1,111 @@
-use crate::Puzzle;
+use std::fmt;
+
+use crate::{Answer, PuzzleError, Solution};
+use crate::aoc::grid::Grid;
 
 // Day4 implements day 4 of AoC 2025, as uploaded at https://adventofcode.com/2025/day/4. 
 pub struct Day4;
+register!(4, "Printing Department", Day4);
 
-fn to_grid(input: &String) -> Vec<Vec<bool>> {
-    return input.split('\n')
-                .map(|line| line.trim().chars().map(|c| c == '@').collect::<Vec<bool>>())
-                .collect::<Vec<Vec<bool>>>();
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    Roll,
+    Empty,
 }
 
-fn surrounding_rolls(grid: &Vec<Vec<bool>>, x: isize, y: isize) -> usize {
-    let mut count = 0;
-    for y_offset in -1..=1 {
-        // don't go off the grid in the y direction
-        if y + y_offset < 0 || y + y_offset >= grid.len() as isize {
-            continue;
-        }
-
-        let new_y = (y + y_offset) as usize;
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", match self {
+            Cell::Roll => '@',
+            Cell::Empty => '.',
+        });
+    }
+}
 
-        for x_offset in -1..=1 {
-            // don't go off the grid in the x direction either
-            if x + x_offset < 0 || (x + x_offset) >= grid[new_y].len() as isize {
-                continue;
-            }
+fn to_grid(input: &str) -> Result<Grid<Cell>, PuzzleError> {
+    return Grid::parse(input, |c| match c {
+        '@' => Some(Cell::Roll),
+        '.' => Some(Cell::Empty),
+        _ => None,
+    });
+}
 
-            // and don't consider the square we're currently looking at
-            if x_offset == 0 && y_offset == 0 {
-                continue;
-            }
+fn surrounding_rolls(grid: &Grid<Cell>, x: usize, y: usize) -> usize {
+    return grid.neighbours8(x, y).filter(|&position| grid[position] == Cell::Roll).count();
+}
 
-            let new_x = (x + x_offset) as usize;
+impl Solution for Day4 {
+    type Parsed<'a> = Grid<Cell>;
 
-            if grid[new_y][new_x] {
-                count += 1;
-            }
-        }
+    fn parse(&self, input: &str) -> Result<Grid<Cell>, PuzzleError> {
+        return to_grid(input);
     }
-    return count;
-}
-
-impl Puzzle for Day4 {
-    fn part1(&self, input: &String) -> String {
-        let grid = to_grid(input);
+    fn part1(&self, grid: &Grid<Cell>) -> Result<Answer, PuzzleError> {
         let mut accessible = 0;
 
-        for y in 0..grid.len() {
-            for x in 0..grid[y].len() {
-                if grid[y][x] && surrounding_rolls(&grid, x as isize, y as isize) < 4 {
-                    //println!("accessible roll at ({x},{y})");
-                    accessible += 1;
-                } 
+        for (x, y) in grid.positions() {
+            if grid[(x, y)] == Cell::Roll && surrounding_rolls(grid, x, y) < 4 {
+                crate::trace!("accessible roll at ({x},{y})");
+                accessible += 1;
             }
         }
-        return accessible.to_string();
+        return Ok(accessible.into());
     }
-    fn part2(&self, input: &String) -> String {
+    fn part2(&self, grid: &Grid<Cell>) -> Result<Answer, PuzzleError> {
         // the tricky thing here is that we might be able to remove from e.g. the last row, so we'll have to continue going until we did a pass without removing any
         // I was lazy and chose to reuse my part 1 code, but a more efficient way of solving this would be to map the entire grid to counts, and propagate to neighbouring nodes
         // i.e. structure it as a graph problem where you visit each node at most 4 times (once 4 of its 8 neighbours have changed) which makes it linear in the size of the input
         // still, this is more than fast enough so no need to overthink it
-        let mut grid = to_grid(input);
+        let mut grid = grid.clone();
         let mut changes = true;
         let mut accessible = 0;
         while changes {
             changes = false;
-            for y in 0..grid.len() {
-                for x in 0..grid[y].len() {
-                    if grid[y][x] && surrounding_rolls(&grid, x as isize, y as isize) < 4 {
-                        //println!("removing accessible roll at ({x},{y})");
-                        accessible += 1;
-                        grid[y][x] = false;
-                        changes = true;
-                    }
+            for (x, y) in grid.positions() {
+                if grid[(x, y)] == Cell::Roll && surrounding_rolls(&grid, x, y) < 4 {
+                    crate::trace!("removing accessible roll at ({x},{y})");
+                    accessible += 1;
+                    grid[(x, y)] = Cell::Empty;
+                    changes = true;
                 }
             }
         }
+        crate::trace!("rolls left:\n{grid}");
 
-        return accessible.to_string();
+        return Ok(accessible.into());
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn surrounding_rolls_counts_all_8_neighbours() {
+        let grid = to_grid("@@@\n@@@\n@@@").unwrap();
+        assert_eq!(surrounding_rolls(&grid, 1, 1), 8);
+    }
+
+    #[test]
+    fn surrounding_rolls_stays_on_the_grid() {
+        let grid = to_grid("@@@\n@@@\n@@@").unwrap();
+        assert_eq!(surrounding_rolls(&grid, 0, 0), 3);
+        assert_eq!(surrounding_rolls(&grid, 2, 1), 5);
+        assert_eq!(surrounding_rolls(&grid, 2, 2), 3);
+    }
+
+    #[test]
+    fn rejects_anything_but_rolls_and_spaces() {
+        let e = to_grid("@.@\n.#.\n").unwrap_err();
+        assert_eq!((e.line, e.column), (Some(2), Some(2)));
+    }
+
+    #[test]
+    fn surrounding_rolls_ignores_empty_spaces() {
+        let grid = to_grid(".@.\n@.@\n...").unwrap();
+        assert_eq!(surrounding_rolls(&grid, 1, 1), 3);
+        assert_eq!(surrounding_rolls(&grid, 0, 0), 2);
+        assert_eq!(surrounding_rolls(&grid, 1, 2), 2);
+    }
+}
@@ -1,94 +1,83 @@
-use crate::Puzzle;
-use std::cmp::min;
-use std::cmp::max;
+use crate::{Answer, PuzzleError, Solution};
+use crate::aoc::interval::IntervalSet;
+use crate::error::column_of;
 // Day5 implements day 5 of AoC 2025, as uploaded at https://adventofcode.com/2025/day/5. 
 pub struct Day5;
+register!(5, "Cafeteria", Day5);
 
-fn process_input(input: &String) -> (Vec<usize>, Vec<usize>, Vec<usize>) {
-    let mut lines = input.lines();
-
-    let mut starts = Vec::new();
-    let mut ends = Vec::new();
-    let fresh_count = lines.by_ref().take_while(|line| line.trim().len() != 0)
-        .map(|line| { 
-            let (start, end) = line.trim().split_once('-').unwrap();
-            starts.push(start.parse::<usize>().unwrap());
-            ends.push(end.parse::<usize>().unwrap());
-        }).count();
-
-    println!("consumed {fresh_count} pairs of fresh ingredients");
-
-    let available = lines.map(|x| x.trim().parse::<usize>().unwrap()).collect::<Vec<usize>>();
-    println!("consumed {} available ingredients", available.len());
-
-    return (starts, ends, available);
+// parse_id parses a single ingredient ID, which is a slice of line (the line_number-th line of the input)
+fn parse_id(line: &str, line_number: usize, id: &str) -> Result<usize, PuzzleError> {
+    return id.parse::<usize>()
+        .map_err(|e| PuzzleError::at(line_number, column_of(line, id), format!("invalid ingredient ID {id:?}: {e}")));
 }
 
-// overlap checks if there is any overlap between ranges X (x1, x2) and Y (y1, y2)
-// the possibilities (same as the booleans) are that X starts within Y, X ends within Y
-// Y starts within X, or Y ends within
-fn overlap(x1: usize, x2: usize, y1: usize, y2: usize) -> bool {
-    return ((y1 <= x1) && (x1 <= y2)) || ((y1 <= x2) && (x2 <= y2)) ||
-           ((x1 <= y1) && (y1 <= x2)) || ((x1 <= y2) && (y2 <= x2));
+// the fresh ranges, then the available ingredients
+// I checked my input playing around in Python and the input ranges _are_ overlapping, so the set merges them as they're added
+#[derive(Debug)]
+pub struct Inventory {
+    fresh: IntervalSet<usize>,
+    available: Vec<usize>,
 }
 
-// add_to_ranges adds a new range to the ranges that already exist
-// we maintain this list in sorted order since adding a new range might cause 2 other ranges to merge together
-// e.g. consider we have ranges 1-2 and 4-5. if we insert range 3-3 this becomes a single range 1-5
-// I checked my input playing around in Python and the input ranges _are_ overlapping so we have to do this
-fn add_to_ranges(ranges: &mut Vec<(usize, usize)>, new_start_p: &usize, new_end_p: &usize) {
-    let (new_start, new_end) = (*new_start_p, *new_end_p);
-    let mut i = 0;
-    while i < ranges.len() {
-        let (existing_start, existing_end) = ranges[i];
-        // cases
-        // no overlap, too early - new_end < existing_start - when we encounter this for the first time, this is a new value! so we need to push it into the array at this point
-        if new_end < existing_start {
-            ranges.insert(i, (new_start, new_end));
-            return;
+fn process_input(input: &str) -> Result<Inventory, PuzzleError> {
+    let mut lines = input.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+    let mut fresh = IntervalSet::new();
+    let mut fresh_count = 0;
+    for (line_number, line) in lines.by_ref().take_while(|(_, line)| !line.trim().is_empty()) {
+        let (start, end) = line.trim().split_once('-')
+            .ok_or_else(|| PuzzleError::at(line_number, 1, format!("fresh range {line:?} has no '-'")))?;
+        let (start, end) = (parse_id(line, line_number, start)?, parse_id(line, line_number, end)?);
+        if end < start {
+            return Err(PuzzleError::at(line_number, 1, format!("fresh range {line:?} ends before it starts")));
         }
+        fresh.insert(start, end);
+        fresh_count += 1;
+    }
 
-        // some overlap - existing_start <= new_end <= existing_end or existing_start <= new_start <= existing_end.
-        // in this case, we have a new range we want to insert - it's inefficient, but correct, to remove the old range, and try and insert this new one instead
-        if overlap(new_start, new_end, existing_start, existing_end) {
-            ranges.remove(i);
-            add_to_ranges(ranges, &min(existing_start, new_start), &max(existing_end, new_end));
-            return;
-        }
+    crate::debug!("consumed {fresh_count} pairs of fresh ingredients, which merge into {}", fresh.iter().count());
 
-        i+=1;
-    }
+    let available = lines.map(|(line_number, x)| parse_id(x, line_number, x.trim())).collect::<Result<Vec<usize>, PuzzleError>>()?;
+    crate::debug!("consumed {} available ingredients", available.len());
 
-    // no overlap, too late
-    // we iterated over the entire array and didn't have any overlap, so this is a new value at the very end
-    ranges.push((new_start, new_end));
+    return Ok(Inventory { fresh, available });
 }
 
-impl Puzzle for Day5 {
-    fn part1(&self, input: &String) -> String {
-        let (starts, ends, available) = process_input(input);
+impl Solution for Day5 {
+    type Parsed<'a> = Inventory;
 
-        let count = available
+    fn parse(&self, input: &str) -> Result<Inventory, PuzzleError> {
+        return process_input(input);
+    }
+    fn part1(&self, inventory: &Inventory) -> Result<Answer, PuzzleError> {
+        let count = inventory.available
             .iter()
-            .filter(|ingredient| {
-                starts.iter().zip(ends.iter())
-                .any(|(start, end)| start <= ingredient && end >= ingredient)
-            })
+            .filter(|&&ingredient| inventory.fresh.contains(ingredient))
             .count();
 
-        return count.to_string();
+        return Ok(count.into());
+    }
+    fn part2(&self, inventory: &Inventory) -> Result<Answer, PuzzleError> {
+        return Ok(inventory.fresh.covered().into());
     }
-    fn part2(&self, input: &String) -> String {
-        let (starts, ends, _) = process_input(input);
+}
 
-        let mut ranges: Vec<(usize, usize)> = Vec::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        let range_count = starts.iter().zip(ends.iter())
-            .map(|(start, end)| add_to_ranges(&mut ranges, start, end))
-            .count();
+    // the merging itself is tested in aoc::interval
 
-        println!("added {range_count} ranges to ranges");
+    #[test]
+    fn fresh_ranges_are_merged() {
+        let inventory = process_input("3-5\n10-14\n16-20\n12-18\n\n1\n5\n").unwrap();
+        assert_eq!(inventory.fresh.iter().collect::<Vec<_>>(), [(3, 5), (10, 20)]);
+        assert_eq!(inventory.available, [1, 5]);
+    }
 
-        return ranges.iter().map(|(start, end)| end - start + 1).sum::<usize>().to_string();
+    #[test]
+    fn rejects_backwards_ranges() {
+        let e = process_input("3-5\n9-7\n\n1\n").unwrap_err();
+        assert_eq!(e.line, Some(2));
     }
 }
@@ -1,7 +1,14 @@
-use crate::Puzzle;
+use crate::{Answer, PuzzleError, Solution};
+use crate::arithmetic::{self, Mode};
+use crate::bigint::BigUint;
+use crate::error::column_of;
+
+use std::fmt;
+use std::str::FromStr;
 
 // Day6 implements day 6 of AoC 2025, as uploaded at https://adventofcode.com/2025/day/6. 
 pub struct Day6;
+register!(6, "Trash Compactor", Day6);
 
 #[derive(Clone, Copy, Debug)]
 enum Operation {
@@ -9,11 +16,31 @@ enum Operation {
     Multiply,
 }
 
-enum Row {
-    Vals(Vec<usize>),
+enum Row<T> {
+    Vals(Vec<T>),
     Ops(Vec<Operation>),
 }
 
+// Value is what the worksheet's values are read as: usize, unless Mode::Big asks for them to be read as BigUints,
+// so that values too big for a usize can still be read (and added or multiplied)
+trait Value: Default + FromStr<Err: fmt::Display> {
+    // push_digit adds a digit to the end of the value, i.e. is self * 10 + digit, or None if that doesn't fit
+    fn push_digit(self, digit: u32) -> Option<Self>;
+}
+
+impl Value for usize {
+    fn push_digit(self, digit: u32) -> Option<usize> {
+        return self.checked_mul(10).and_then(|v| v.checked_add(digit as usize));
+    }
+}
+
+impl Value for BigUint {
+    fn push_digit(mut self, digit: u32) -> Option<BigUint> {
+        self.mul_add_small(10, digit);
+        return Some(self);
+    }
+}
+
 fn parse_op(v: char) -> Option<Operation> {
     match v {
         '*' => Some(Operation::Multiply),
@@ -22,131 +49,206 @@ fn parse_op(v: char) -> Option<Operation> {
     }
 }
 
-fn parse_line(line: &str) -> Row {
+// line_number is only used to say where any error was
+fn parse_line<T: Value>(line: &str, line_number: usize) -> Result<Row<T>, PuzzleError> {
     let vals_or_ops = line.split_whitespace();
 
     let mut vals = Vec::new();
     let mut ops = Vec::new();
 
-    vals_or_ops.map(|v: &str| {
-        match parse_op(v.chars().nth(0).unwrap()) {
+    for v in vals_or_ops {
+        // split_whitespace never gives us an empty string, so there's always a first char
+        match parse_op(v.chars().next().unwrap()) {
             Some(op) => ops.push(op),
-            // we deliberately expect this unwrap to error if it's not an op or a val
-            None => vals.push(v.parse::<usize>().unwrap()),
-        }    
-    }).count();
+            // if it's not an op it has to be a val
+            None => vals.push(v.parse::<T>().map_err(|e| {
+                PuzzleError::at(line_number, column_of(line, v), format!("{v:?} is neither a value nor an operation: {e}"))
+            })?),
+        }
+    }
 
     match (vals.len(), ops.len()) {
-        (0, 0) => panic!("received empty line"),
-        (0, _l) => Row::Ops(ops),
-        (_l, 0) => Row::Vals(vals),
-        (l_v, l_o) => panic!("invalid line contained both {l_v} vals and {l_o} ops"),
+        (0, 0) => Err(PuzzleError::at(line_number, 1, "received empty line")),
+        (0, _l) => Ok(Row::Ops(ops)),
+        (_l, 0) => Ok(Row::Vals(vals)),
+        (l_v, l_o) => Err(PuzzleError::at(line_number, 1, format!("invalid line contained both {l_v} vals and {l_o} ops"))),
     }
 }
 
-// map_input maps from cephalopod math to natural math 
-fn map_input(input: &str) -> Vec<(Vec<usize>, Operation)> {
-    let mut lines = input.lines().collect::<Vec<&str>>();
+// Worksheet is the input split into the operations, and the lines of values as they are in the input
+// the values are left unparsed because the two parts read them differently (by row for part 1, and by column for part 2)
+pub struct Worksheet<'a> {
+    value_lines: Vec<&'a str>,
+    ops: Vec<Operation>,
+}
+
+fn parse_worksheet(input: &str) -> Result<Worksheet<'_>, PuzzleError> {
+    let mut value_lines = input.lines().collect::<Vec<&str>>();
 
     // the last line should be all the operations, so pop it off
-    let ops = match parse_line(lines.pop().unwrap()) {
+    let ops_line = value_lines.pop().ok_or_else(|| PuzzleError::new("input was empty"))?;
+    let ops = match parse_line::<usize>(ops_line, value_lines.len() + 1)? {
         Row::Ops(o) => o,
-        _ => panic!("final row was not ops"),
+        _ => return Err(PuzzleError::at(value_lines.len() + 1, 1, "final row was not ops")),
     };
-    
-    // we shouldn't need to read the whole input into an array, this is wasteful, but easier to write
-    let mut line_iters: Vec<std::str::Chars<'_>> = lines.iter().map(|line| line.chars()).collect();
+    return Ok(Worksheet { value_lines, ops });
+}
+
+// map_input maps from cephalopod math to natural math 
+fn map_input<T: Value>(worksheet: &Worksheet) -> Result<Vec<(Vec<T>, Operation)>, PuzzleError> {
+    // each line is walked backwards a column at a time, in step with the others, without copying any of it
+    let mut line_iters: Vec<std::str::Chars<'_>> = worksheet.value_lines.iter().map(|line| line.chars()).collect();
     
     let mut problems = Vec::new();
     let mut problem = Vec::new();
-    let mut non_space = false;
     loop {
-        let mut new_val = "".to_string();
-        for j in 0..lines.len() {
-            match line_iters[j].nth_back(0) {
+        // the digits of a value are read top to bottom, so it can be built up as we go rather than collected into a string to parse
+        let mut new_val: Option<T> = None;
+        for (i, line_iter) in line_iters.iter_mut().enumerate() {
+            match line_iter.next_back() {
                 // None => we exhausted the rows, so return the problem
                 // we parsed the problems in reverse, so we need to reverse ops too
                 None => {
-                    problems.push(problem.clone());
-                    return problems.into_iter().zip(ops.into_iter().rev()).collect();
+                    problems.push(problem);
+                    return Ok(problems.into_iter().zip(worksheet.ops.iter().rev().copied()).collect());
                 },
                 // a space - not interesting
                 Some(' ') => continue,
                 Some(c) => {
-                    non_space = true;
-                    new_val.push(c);
+                    // the line is known here, but not the column: that's counted from the end, and lines can be different lengths
+                    let digit = c.to_digit(10)
+                        .ok_or_else(|| PuzzleError::new(format!("line {} has {c:?} in a column of values", i + 1)))?;
+                    new_val = new_val.unwrap_or_default().push_digit(digit)
+                        .map(Some)
+                        .ok_or_else(|| PuzzleError::new(format!("a column of values is too big for a usize, at line {}", i + 1)))?;
                 }
             }
         }
 
-        // this was a value other than all spaces, so it's a new value for the problem 
-        if non_space {
-            problem.push(new_val.parse::<usize>().unwrap());
-            non_space = false;
-        } else {
+        match new_val {
+            // this was a value other than all spaces, so it's a new value for the problem 
+            Some(val) => problem.push(val),
             // this _was_ all spaces, so we hit the end of this problem
-            problems.push(problem.clone());
-            problem.clear();
+            None => problems.push(std::mem::take(&mut problem)),
         }
     } 
 
 
 }
 
-fn parse_input<'a, I>(lines: I) -> Vec<(Vec<usize>, Operation)>
-where I : Iterator<Item = String> {
-    let mut problem_vals: Vec<Vec<usize>> = Vec::new();
+fn parse_input<T: Value>(worksheet: &Worksheet) -> Result<Vec<(Vec<T>, Operation)>, PuzzleError> {
+    let mut problem_vals: Vec<Vec<T>> = Vec::new();
 
-    let mut problem_ops = None;
-
-    for row in lines.map(|x| parse_line(x.trim())) {
-        match row {
+    for (i, line) in worksheet.value_lines.iter().enumerate() {
+        match parse_line(line, i + 1)? {
             Row::Vals(vals) => {
                 // if this is the first row, we need to initialise the vals for each row
-                if problem_vals.len() == 0 {
+                if problem_vals.is_empty() {
                     for v in vals {
-                        let mut new_problem = Vec::new();
-                        new_problem.push(v);
-                        problem_vals.push(new_problem);
+                        problem_vals.push(vec![v]);
                     }
                 } else {
-                    for (i, v) in vals.into_iter().enumerate() {
-                        problem_vals[i].push(v);
+                    for (j, v) in vals.into_iter().enumerate() {
+                        match problem_vals.get_mut(j) {
+                            Some(problem) => problem.push(v),
+                            None => return Err(PuzzleError::at(i + 1, 1, format!("row has more than the {} values of the first row", problem_vals.len()))),
+                        }
                     }
                 }
             }
-            Row::Ops(ops) => problem_ops = Some(ops),
+            Row::Ops(_) => return Err(PuzzleError::at(i + 1, 1, "only the last row can be ops")),
         }
     }
 
-    if problem_ops.is_none() {
-        panic!("input had no operations row");
-    }
-
     // zip each set of vals with its corresponding op, and return that
-    return problem_vals.into_iter().zip(
-        problem_ops.unwrap().into_iter()
-    ).collect();
+    return Ok(problem_vals.into_iter().zip(worksheet.ops.iter().copied()).collect());
 
 }
 
-impl Puzzle for Day6 {
-    fn part1(&self, input: &String) -> String {
-        return parse_input(input.lines().map(|x| x.to_string())).into_iter().map(|(vals, op)| {
-            match op {
-                Operation::Add => vals.iter().sum::<usize>(),
-                Operation::Multiply => vals.iter().product(),
-            }
-        }).sum::<usize>().to_string();
+// evaluate_big works out every problem and adds up the results in arbitrary precision, for Mode::Big, which always gets the right answer
+// (the values were read as BigUints too, so it doesn't matter how big they are either)
+fn evaluate_big(problems: Vec<(Vec<BigUint>, Operation)>) -> Answer {
+    return problems.into_iter().map(|(vals, op)| {
+        match op {
+            Operation::Add => vals.into_iter().sum::<BigUint>(),
+            Operation::Multiply => vals.into_iter().product(),
+        }
+    }).sum::<BigUint>().into();
+}
+
+// evaluate works out every problem and adds up the results, overflowing (or not) however the mode says to
+// the products in particular can get big, which is what Mode::Big is for
+fn evaluate(problems: Vec<(Vec<usize>, Operation)>, mode: Mode) -> Result<Answer, PuzzleError> {
+    let mut total: usize = 0;
+    for (i, (vals, op)) in problems.into_iter().enumerate() {
+        crate::trace!("evaluating {vals:?} under {op:?}");
+        let result = match op {
+            Operation::Add => mode.sum(vals, format_args!("the sum of problem {}", i + 1))?,
+            Operation::Multiply => mode.product(vals, format_args!("the product of problem {}", i + 1))?,
+        };
+        total = mode.add(total, result, "the grand total")?;
     }
-    fn part2(&self, input: &String) -> String {
-        return map_input(input).into_iter().map(|(vals, op)| {
-            println!("evaluating {vals:?} under {op:?}");
-            match op {
-                Operation::Add => vals.iter().sum::<usize>(),
-                Operation::Multiply => vals.iter().product(),
-            }
-        }).sum::<usize>().to_string();
+    return Ok(total.into());
+}
+
+impl Solution for Day6 {
+    type Parsed<'a> = Worksheet<'a>;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Worksheet<'a>, PuzzleError> {
+        return parse_worksheet(input);
+    }
+    fn part1(&self, worksheet: &Worksheet) -> Result<Answer, PuzzleError> {
+        return match arithmetic::mode() {
+            Mode::Big => Ok(evaluate_big(parse_input(worksheet)?)),
+            mode => evaluate(parse_input(worksheet)?, mode),
+        };
+    }
+    fn part2(&self, worksheet: &Worksheet) -> Result<Answer, PuzzleError> {
+        return match arithmetic::mode() {
+            Mode::Big => Ok(evaluate_big(map_input(worksheet)?)),
+            mode => evaluate(map_input(worksheet)?, mode),
+        };
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // three 10 digit numbers multiply to 30 digits, which is far more than a usize can hold
+    const HUGE: &str = "9999999999 1\n9999999999 2\n9999999999 3\n*          +\n";
+
+    #[test]
+    fn checked_overflow_says_which_problem() {
+        let worksheet = parse_worksheet(HUGE).unwrap();
+        let e = evaluate(parse_input(&worksheet).unwrap(), Mode::Checked).unwrap_err();
+        assert!(e.message.starts_with("overflow in the product of problem 1: "), "{e}");
+    }
+
+    #[test]
+    fn big_arithmetic_gets_the_right_answer() {
+        let worksheet = parse_worksheet(HUGE).unwrap();
+        let answer = evaluate_big(parse_input(&worksheet).unwrap());
+        assert_eq!(answer.to_string(), "999999999700000000030000000005");
+        assert_eq!(answer.to_string().len(), 30);
+        assert_eq!(answer.kind(), "big_integer");
+
+        // and small answers are just as they would be otherwise
+        let worksheet = parse_worksheet("12 3\n4 5\n+ *\n").unwrap();
+        assert_eq!(Ok(evaluate_big(parse_input(&worksheet).unwrap())), evaluate(parse_input(&worksheet).unwrap(), Mode::Checked));
+    }
+
+    #[test]
+    fn big_arithmetic_reads_values_too_big_for_a_usize() {
+        // part 1 reads the first row as a 27 digit value
+        let worksheet = parse_worksheet("123456789012345678901234567\n1\n+\n").unwrap();
+        assert!(parse_input::<usize>(&worksheet).is_err());
+        assert_eq!(evaluate_big(parse_input(&worksheet).unwrap()).to_string(), "123456789012345678901234568");
+
+        // part 2 reads 21 nines down the column as one value, and a usize can only hold 20 digits
+        let column = format!("{}+\n", "9\n".repeat(21));
+        let worksheet = parse_worksheet(&column).unwrap();
+        assert_eq!(map_input::<usize>(&worksheet).unwrap_err().message, "a column of values is too big for a usize, at line 20");
+        assert_eq!(evaluate_big(map_input(&worksheet).unwrap()).to_string(), "9".repeat(21));
+    }
+}
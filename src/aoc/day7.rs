@@ -1,9 +1,13 @@
-use crate::Puzzle;
+use crate::{Answer, PuzzleError, Solution};
+use crate::arithmetic;
+use crate::aoc::grid::Grid;
 
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 // Day7 implements day 7 of AoC 2025, as uploaded at https://adventofcode.com/2025/day/7. 
 pub struct Day7;
+register!(7, "Laboratories", Day7);
 
 // important info:
 // starts at S, splits at ^, passes through .
@@ -15,56 +19,146 @@ pub struct Day7;
 
 // part 1 - how many times will it be split - i.e. the number of times we have a beam hit a splitter
 
-impl Puzzle for Day7 {
-    fn part1(&self, input: &String) -> String {
-        let mut lines = input.lines();
-        let first_pos = lines.next().unwrap().chars().position(|x| x == 'S').unwrap();
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    Start,
+    Splitter,
+    Empty,
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", match self {
+            Cell::Start => 'S',
+            Cell::Splitter => '^',
+            Cell::Empty => '.',
+        });
+    }
+}
+
+fn parse_manifold(input: &str) -> Result<Grid<Cell>, PuzzleError> {
+    return Grid::parse(input, |c| match c {
+        'S' => Some(Cell::Start),
+        '^' => Some(Cell::Splitter),
+        '.' => Some(Cell::Empty),
+        _ => None,
+    });
+}
+
+// start_position finds where the beam enters the manifold, i.e. the S
+fn start_position(grid: &Grid<Cell>) -> Result<(usize, usize), PuzzleError> {
+    if grid.height() == 0 {
+        return Err(PuzzleError::new("input was empty"));
+    }
+    return grid.find(&Cell::Start).ok_or_else(|| PuzzleError::new("there's no S for the beam to start from"));
+}
+
+// beside is where the two halves of a beam split at column i go: the columns either side of it, or None for a half that's gone off the
+// edge of the grid (and so out of the manifold, where there's nothing left for it to hit)
+fn beside(i: usize, width: usize) -> [Option<usize>; 2] {
+    return [i.checked_sub(1), Some(i + 1).filter(|&j| j < width)];
+}
+
+// splitters are the x positions of the splitters in each row below the S, from the top
+fn splitters(grid: &Grid<Cell>, start_y: usize) -> impl Iterator<Item = impl Iterator<Item = usize>> {
+    return grid.rows().skip(start_y + 1).map(|row| {
+        row.iter().enumerate().filter(|(_, c)| **c == Cell::Splitter).map(|(i, _)| i)
+    });
+}
+
+impl Solution for Day7 {
+    type Parsed<'a> = Grid<Cell>;
+
+    fn parse(&self, input: &str) -> Result<Grid<Cell>, PuzzleError> {
+        return parse_manifold(input);
+    }
+    fn part1(&self, grid: &Grid<Cell>) -> Result<Answer, PuzzleError> {
+        let (first_pos, start_y) = start_position(grid)?;
         let mut positions = HashSet::<usize>::new();
         positions.insert(first_pos);
 
         let mut total_splits = 0;
-        for (y, line) in lines.enumerate() {
-            let splitter_indices = line.chars().enumerate().filter(|(_, c)| *c == '^').map(|(i, _)| i);
+        for (y, splitter_indices) in splitters(grid, start_y).enumerate() {
             for i in splitter_indices {
                 // if there is a beam at the specified position, then remove it and add the new positions
                 if positions.remove(&i) {
-                    // NB: we might need to handle hitting the edge of the map in the general case but neither the test nor my puzzle input include that 
-                    positions.insert(i-1);
-                    positions.insert(i+1);
+                    positions.extend(beside(i, grid.width()).into_iter().flatten());
                     total_splits += 1;
-                    println!("beam split at position {i}");
+                    crate::trace!("beam split at position {i}");
                 }
             }
 
-            println!("beam positions at y={y}: {positions:?}");
+            crate::trace!("beam positions at y={y}: {positions:?}");
         }
 
-        return total_splits.to_string();
+        return Ok(total_splits.into());
     }
-    fn part2(&self, input: &String) -> String {
-        let mut lines = input.lines();
-        let first_pos = lines.next().unwrap().chars().position(|x| x == 'S').unwrap();
+    fn part2(&self, grid: &Grid<Cell>) -> Result<Answer, PuzzleError> {
+        let (first_pos, start_y) = start_position(grid)?;
         let mut positions = HashMap::<usize, usize>::new();
         positions.insert(first_pos, 1);
+        // the timelines whose beam went off the side of the grid, which still count even though they can't be split any more
+        let mut escaped = 0;
 
-        for (y, line) in lines.enumerate() {
-            let splitter_indices = line.chars().enumerate().filter(|(_, c)| *c == '^').map(|(i, _)| i);
+        for (y, splitter_indices) in splitters(grid, start_y).enumerate() {
             for i in splitter_indices {
                 // if there is a beam at the specified position, then remove it and add the new positions
                 match positions.remove(&i) {
                     None => continue,
                     Some(v) => {
                         // it may have already been possible to get to either of the neighbouring positions, so we need to attempt to pull out and update the neighbouring values
-                        positions.insert(i-1, positions.get(&(i-1)).unwrap_or(&0) + v);
-                        positions.insert(i+1, positions.get(&(i+1)).unwrap_or(&0) + v);
+                        // (each splitter doubles the timelines, so these can get big)
+                        let what = "the number of timelines reaching a position";
+                        for next in beside(i, grid.width()) {
+                            match next {
+                                Some(j) => { positions.insert(j, arithmetic::add(*positions.get(&j).unwrap_or(&0), v, what)?); },
+                                None => escaped = arithmetic::add(escaped, v, what)?,
+                            }
+                        }
                     }
                 }
             }
 
-            println!("beam positions at y={y}: {positions:?}");
+            crate::trace!("beam positions at y={y}: {positions:?}");
         }
 
         // the number of different timelines total is the sum of the number of timelines that can get to each position
-        return positions.iter().map(|(_,v)| v).sum::<usize>().to_string();
+        return Ok(arithmetic::sum(positions.values().copied().chain([escaped]), "the total number of timelines")?.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_beam_starts_at_the_s() {
+        let grid = parse_manifold("..S..\n.....\n..^..\n").unwrap();
+        assert_eq!(start_position(&grid), Ok((2, 0)));
+        assert_eq!(grid.to_string(), "..S..\n.....\n..^..");
+        assert!(start_position(&parse_manifold("...\n").unwrap()).is_err());
+        assert!(start_position(&parse_manifold("").unwrap()).is_err());
+    }
+
+    #[test]
+    fn splitters_are_found_in_each_row_below_the_start() {
+        let grid = parse_manifold("^.^\n.S.\n^.^\n.^.\n").unwrap();
+        assert_eq!(splitters(&grid, 1).map(|row| row.collect::<Vec<_>>()).collect::<Vec<_>>(), [vec![0, 2], vec![1]]);
+    }
+
+    #[test]
+    fn beams_can_split_off_the_left_edge() {
+        let grid = parse_manifold("S..\n...\n^..\n").unwrap();
+        assert_eq!(Solution::part1(&Day7, &grid), Ok(Answer::from(1)));
+        // the half that went off the edge is still a timeline
+        assert_eq!(Solution::part2(&Day7, &grid), Ok(Answer::from(2)));
+    }
+
+    #[test]
+    fn beams_can_split_off_the_right_edge() {
+        let grid = parse_manifold("..S\n..^\n.^.\n").unwrap();
+        // only the half that stayed on the grid reaches the second splitter
+        assert_eq!(Solution::part1(&Day7, &grid), Ok(Answer::from(2)));
+        assert_eq!(Solution::part2(&Day7, &grid), Ok(Answer::from(3)));
     }
 }
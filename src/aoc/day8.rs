@@ -1,10 +1,12 @@
-use crate::Puzzle;
-use typenum;
+use crate::{Answer, PuzzleError, Solution};
+use crate::arithmetic;
+use crate::error::column_of;
 use kd_tree::{KdTree};
 use std::collections::{HashSet};
 
 // Day8 implements day 8 of AoC 2025, as uploaded at https://adventofcode.com/2025/day/8. 
 pub struct Day8;
+register!(8, "Playground", Day8);
 
 // given 1000 sets of coordinates in 3D space, work out how close each pair is to one another, and take the closest pair
 // part 1) join the 10 closest pairs the size of the 3 largest circuits multiplied together
@@ -19,7 +21,7 @@ pub struct Day8;
 //   I'm sure I'd learn a lot from implementing this from scratch but when there's a crate just there... I may as well learn from just the code anyway.
 // (I'm very glad I did this, it took a couple of hours to solve this one)
 #[derive(Clone, Copy, Debug)]
-struct Point {
+pub struct Point {
     i: usize,
     x: usize,
     y: usize,
@@ -40,33 +42,47 @@ impl kd_tree::KdPoint for Point {
     }
 }
 
-fn parse_input(input: &str) -> (KdTree<Point>, Vec<Point>) {
+// the kd-tree works out squared distances in isizes, which can't overflow as long as no coordinate is more than 2^30
+// (that's 3 * 2^60 at most), so anything bigger is rejected rather than panicking in there
+const MAX_COORDINATE: usize = 1 << 30;
+
+fn parse_input(input: &str) -> Result<(KdTree<Point>, Vec<Point>), PuzzleError> {
     let mut points = Vec::<Point>::new();
-    for (i, mut coords) in input.lines()
-                      .map(|line| line.trim()
-                                            .split(',')
-                                            .map(|x| x.parse::<usize>().unwrap())
-                      ).enumerate() {
+    for (i, line) in input.lines().enumerate() {
+        let line_number = i + 1;
+        let mut coords = line.trim()
+                             .split(',')
+                             .map(|x| match x.parse::<usize>() {
+                                 Ok(v) if v <= MAX_COORDINATE => Ok(v),
+                                 Ok(_) => Err(PuzzleError::at(line_number, column_of(line, x), format!("coordinate {x} is bigger than {MAX_COORDINATE}"))),
+                                 Err(e) => Err(PuzzleError::at(line_number, column_of(line, x), format!("invalid coordinate {x:?}: {e}"))),
+                             });
+        let mut next_coord = || coords.next()
+            .unwrap_or_else(|| Err(PuzzleError::at(line_number, 1, "fewer than 3 values on line")));
+
         let p = Point{
-            i: i,
-            x: coords.next().unwrap(),
-            y: coords.next().unwrap(),
-            z: coords.next().unwrap(),
+            i,
+            x: next_coord()?,
+            y: next_coord()?,
+            z: next_coord()?,
         };
 
-        let next = coords.next();
-
-        match next {
-            Some(_) => panic!("more than 3 values on line {i} of input"),
-            None => {}, // I'm sure this isn't idiomatic but it works
+        if coords.next().is_some() {
+            return Err(PuzzleError::at(line_number, 1, "more than 3 values on line"));
         }
 
         points.push(p);
     }
 
-    return (KdTree::build(points.clone()), points);
+    // with fewer than 2 boxes there aren't any pairs to connect, for either part
+    if points.len() < 2 {
+        return Err(PuzzleError::new(format!("need at least 2 junction boxes, but there are {}", points.len())));
+    }
+
+    return Ok((KdTree::build(points.clone()), points));
 }
 
+#[allow(dead_code)]
 fn square_diff(v1: usize, v2: usize) -> usize {
     if v1 > v2 {
         return (v1 - v2) * (v1 - v2);
@@ -75,6 +91,7 @@ fn square_diff(v1: usize, v2: usize) -> usize {
     }
 }
 
+#[allow(dead_code)]
 fn square_distance(p1: Point, p2: Point) -> usize {
     return square_diff(p1.x, p2.x) + square_diff(p1.y, p2.y) + square_diff(p1.z, p2.z); 
 }
@@ -99,21 +116,24 @@ fn initialise_circuits(length: usize) -> Vec::<HashSet<usize>> {
 }
 
 // n is 0-indexed, i.e. 0 returns the closest point, 1 returns the next closest point 
-fn nth_closest(tree: &KdTree<Point>, point: &Point, n: usize) -> PointDist {
+// this is None once n has gone past every other point in the tree
+fn nth_closest(tree: &KdTree<Point>, point: &Point, n: usize) -> Option<PointDist> {
     // n=0 actually returns the second closest point in the tree (since the closest one is always that same point)
-    let nearest = tree.nearests(point, n + 2)[n+1];
-    return PointDist{
+    let nearest = *tree.nearests(point, n + 2).get(n + 1)?;
+    return Some(PointDist{
         i_point_a: point.i,
         i_point_b: nearest.item.i,
         dist: nearest.squared_distance,
-    }
+    });
 }
 
 fn insert_next(tree: &KdTree<Point>, distances: &mut Vec<PointDist>, point: &Point, n: usize) {
-    // get the next closest point...
-    let next_point = nth_closest(tree, point, n);
+    // get the next closest point, if the point hasn't already been paired with every other one...
+    let Some(next_point) = nth_closest(tree, point, n) else {
+        return;
+    };
 
-    //println!("inserting new distance {next_point:?}");
+    crate::trace!("inserting new distance {next_point:?}");
 
     // and insert it at the correct place in the array. it's ordered with smallest last, so we need to iterate over the array from the end
     for i in (0..distances.len()).rev() {
@@ -128,7 +148,23 @@ fn insert_next(tree: &KdTree<Point>, distances: &mut Vec<PointDist>, point: &Poi
     distances.insert(0, next_point);
 }
 
-fn part1(tree: KdTree<Point>, points: Vec<Point>) -> usize {
+// pop_pair pops the next closest pair off the end of distances
+fn pop_pair(distances: &mut Vec<PointDist>) -> Result<(PointDist, PointDist), PuzzleError> {
+    // cheeky trick assuming that there are no ties: we should be able to pop two points and get two that point to one another
+    let (first, second) = match (distances.pop(), distances.pop()) {
+        (Some(first), Some(second)) => (first, second),
+        _ => return Err(PuzzleError::new("ran out of pairs of junction boxes to connect")),
+    };
+
+    if first.i_point_a != second.i_point_b || first.i_point_b != second.i_point_a {
+        return Err(PuzzleError::new(format!("popped two points which didn't refer to one another {first:?} {second:?}")));
+    }
+
+    return Ok((first, second));
+}
+
+// part1_with connects the closest `pairs` pairs: the puzzle connects 1000 for the real input, but only 10 for its example
+fn part1_with(tree: &KdTree<Point>, points: &[Point], pairs: usize) -> Result<usize, PuzzleError> {
     // unfortunately the library I'm using doesn't support deleting points from a tree once it's already constructed
     // however we can use a cheeky workaround which isn't the most efficient, but is more than good enough
     //
@@ -137,7 +173,7 @@ fn part1(tree: KdTree<Point>, points: Vec<Point>) -> usize {
     // then we also need to keep track of the circuits - easiest way of doing this is going to be having a map of points to the circuits they belong to as a pointer to a set
     // nb. indices must start at 1 because every point's closest point is otherwise itself. I don't think this even assumes there are no duplicates.
     let mut position_map = tree.iter().map(|_| 0_usize).collect::<Vec<usize>>();
-    let mut distances = tree.iter().map(|point| nth_closest(&tree, point, 0)).collect::<Vec<PointDist>>(); 
+    let mut distances = tree.iter().filter_map(|point| nth_closest(tree, point, 0)).collect::<Vec<PointDist>>(); 
     
     let mut circuits = initialise_circuits(tree.len());
     let mut circuit_map = circuits.iter().enumerate().map(|(i, _)| i).collect::<Vec<usize>>();
@@ -154,30 +190,24 @@ fn part1(tree: KdTree<Point>, points: Vec<Point>) -> usize {
     // these should be pretty rare in a 3D space (depending how points are distributed, hopefully not a grid...)
     // given there's no discussion of how to handle ties in the problem description let's just say it's out of scope at least to start off with and hope it doesn't bite me for part 2
     
-    // comparing b to a gives reversed (descending) order
-    distances.sort_by(|a, b| b.dist.cmp(&a.dist));
-    //println!("distances: {:?}", distances);
-    for _ in 0..1000 {
-        // cheeky trick assuming that there are no ties: we should be able to pop two points and get two that point to one another
-        let first = distances.pop().unwrap();
-        let second = distances.pop().unwrap();
-
-        if first.i_point_a != second.i_point_b || first.i_point_b != second.i_point_a {
-            panic!("popped two points which didn't refer to one another {first:?} {second:?}");
-        }
+    // sorting by the reversed distance gives descending order
+    distances.sort_by_key(|d| std::cmp::Reverse(d.dist));
+    crate::trace!("distances: {:?}", distances);
+    for _ in 0..pairs {
+        let (first, _) = pop_pair(&mut distances)?;
 
         // we have our closest pair! now we need to insert the next new points for each, then connect them if we need to
         let i_a = first.i_point_a;
         let i_b = first.i_point_b;
         
-        //println!("pairing {:?} with {:?} with distance {}", points[i_a], points[i_b], first.dist);
+        crate::trace!("pairing {:?} with {:?} with distance {}", points[i_a], points[i_b], first.dist);
 
         // now fetch and insert the next closest point for each of the two 
         position_map[i_a] += 1;
         position_map[i_b] += 1;
 
-        insert_next(&tree, &mut distances, &points[i_a], position_map[i_a]);
-        insert_next(&tree, &mut distances, &points[i_b], position_map[i_b]);
+        insert_next(tree, &mut distances, &points[i_a], position_map[i_a]);
+        insert_next(tree, &mut distances, &points[i_b], position_map[i_b]);
 
         let c_i_a = circuit_map[i_a];
         let c_i_b = circuit_map[i_b];
@@ -215,11 +245,11 @@ fn part1(tree: KdTree<Point>, points: Vec<Point>) -> usize {
     // all of the ones which are no longer connected are now empty, so they will have length 0
     let mut circuit_sizes = circuits.iter().map(|s| s.len()).collect::<Vec<usize>>();
     circuit_sizes.sort();
-    return circuit_sizes.iter().rev().take(3).product();
+    return arithmetic::product(circuit_sizes.iter().rev().take(3).copied(), "the product of the three largest circuits' sizes");
 }
 
 // it's late and I'm tired so I'm just copying and modifying my part1 for part 2 rather than deduplicating them (using a predicate over circuit_sizes)
-fn part2(tree: KdTree<Point>, points: Vec<Point>) -> usize {
+fn part2(tree: &KdTree<Point>, points: &[Point]) -> Result<usize, PuzzleError> {
     // unfortunately the library I'm using doesn't support deleting points from a tree once it's already constructed
     // however we can use a cheeky workaround which isn't the most efficient, but is more than good enough
     //
@@ -228,7 +258,7 @@ fn part2(tree: KdTree<Point>, points: Vec<Point>) -> usize {
     // then we also need to keep track of the circuits - easiest way of doing this is going to be having a map of points to the circuits they belong to as a pointer to a set
     // nb. indices must start at 1 because every point's closest point is otherwise itself. I don't think this even assumes there are no duplicates.
     let mut position_map = tree.iter().map(|_| 0_usize).collect::<Vec<usize>>();
-    let mut distances = tree.iter().map(|point| nth_closest(&tree, point, 0)).collect::<Vec<PointDist>>(); 
+    let mut distances = tree.iter().filter_map(|point| nth_closest(tree, point, 0)).collect::<Vec<PointDist>>(); 
     
     let mut circuits = initialise_circuits(tree.len());
     let mut circuit_map = circuits.iter().enumerate().map(|(i, _)| i).collect::<Vec<usize>>();
@@ -246,33 +276,27 @@ fn part2(tree: KdTree<Point>, points: Vec<Point>) -> usize {
     // these should be pretty rare in a 3D space (depending how points are distributed, hopefully not a grid...)
     // given there's no discussion of how to handle ties in the problem description let's just say it's out of scope at least to start off with and hope it doesn't bite me for part 2
     
-    // comparing b to a gives reversed (descending) order
-    distances.sort_by(|a, b| b.dist.cmp(&a.dist));
-    //println!("distances: {:?}", distances);
+    // sorting by the reversed distance gives descending order
+    distances.sort_by_key(|d| std::cmp::Reverse(d.dist));
+    crate::trace!("distances: {:?}", distances);
 
     let mut point_a: Option<Point> = None;
     let mut point_b: Option<Point> = None;
     while circuits_removed < circuits.len() - 1 {
-        // cheeky trick assuming that there are no ties: we should be able to pop two points and get two that point to one another
-        let first = distances.pop().unwrap();
-        let second = distances.pop().unwrap();
-
-        if first.i_point_a != second.i_point_b || first.i_point_b != second.i_point_a {
-            panic!("popped two points which didn't refer to one another {first:?} {second:?}");
-        }
+        let (first, _) = pop_pair(&mut distances)?;
 
         // we have our closest pair! now we need to insert the next new points for each, then connect them if we need to
         let i_a = first.i_point_a;
         let i_b = first.i_point_b;
         
-        //println!("pairing {:?} with {:?} with distance {}", points[i_a], points[i_b], first.dist);
+        crate::trace!("pairing {:?} with {:?} with distance {}", points[i_a], points[i_b], first.dist);
 
         // now fetch and insert the next closest point for each of the two 
         position_map[i_a] += 1;
         position_map[i_b] += 1;
 
-        insert_next(&tree, &mut distances, &points[i_a], position_map[i_a]);
-        insert_next(&tree, &mut distances, &points[i_b], position_map[i_b]);
+        insert_next(tree, &mut distances, &points[i_a], position_map[i_a]);
+        insert_next(tree, &mut distances, &points[i_b], position_map[i_b]);
 
         let c_i_a = circuit_map[i_a];
         let c_i_b = circuit_map[i_b];
@@ -309,22 +333,78 @@ fn part2(tree: KdTree<Point>, points: Vec<Point>) -> usize {
         // if I were writing C this is where I'd free the old circuit
     }
     
-    println!("largest index was {}", position_map.iter().max().unwrap());
+    crate::debug!("largest index was {}", position_map.iter().max().unwrap_or(&0));
 
     // we now have our one big circuit, and we were keeping track of our last selected point. so just return the two x coords
     return match (point_a, point_b) {
-        (Some(a), Some(b)) => a.x * b.x,
-        _ => panic!("ended part 2 without two points (how? you should be in an infinite loop)")
+        (Some(a), Some(b)) => arithmetic::mul(a.x, b.x, "the product of the last two junction boxes' x coordinates"),
+        // (this can happen if there's only one junction box, since it starts out connected to everything)
+        _ => Err(PuzzleError::new("ended part 2 without two points")),
     }
 }
-impl Puzzle for Day8 {
-    fn part1(&self, input: &String) -> String {
-        let (tree, points) = parse_input(input); 
-        return part1(tree, points).to_string(); 
+// the tree is built once for both parts
+impl Solution for Day8 {
+    type Parsed<'a> = (KdTree<Point>, Vec<Point>);
+
+    fn parse(&self, input: &str) -> Result<(KdTree<Point>, Vec<Point>), PuzzleError> {
+        return parse_input(input);
     }
-    fn part2(&self, input: &String) -> String {
-        let (tree, points) = parse_input(input); 
-        return part2(tree, points).to_string(); 
+    fn part1(&self, (tree, points): &(KdTree<Point>, Vec<Point>)) -> Result<Answer, PuzzleError> {
+        return Ok(part1_with(tree, points, 1000)?.into());
+    }
+    fn part2(&self, (tree, points): &(KdTree<Point>, Vec<Point>)) -> Result<Answer, PuzzleError> {
+        return Ok(part2(tree, points)?.into());
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Puzzle;
+
+    fn point(x: usize, y: usize, z: usize) -> Point {
+        return Point { i: 0, x, y, z };
+    }
+
+    #[test]
+    fn square_distance_sums_squared_differences() {
+        assert_eq!(square_distance(point(0, 0, 0), point(1, 2, 3)), 14);
+        assert_eq!(square_distance(point(162, 817, 812), point(425, 690, 689)), 263 * 263 + 127 * 127 + 123 * 123);
+    }
+
+    #[test]
+    fn square_distance_is_symmetric() {
+        // which way round the points are matters for unsigned subtraction, so check both
+        assert_eq!(square_distance(point(5, 1, 9), point(2, 7, 3)), square_distance(point(2, 7, 3), point(5, 1, 9)));
+        assert_eq!(square_distance(point(4, 4, 4), point(4, 4, 4)), 0);
+    }
+
+    #[test]
+    fn example_with_ten_pairs() {
+        // the example harness can only run part 1 with 1000 pairs, which is more than the example's 20 boxes have
+        let (tree, points) = parse_input(include_str!("../../examples/day8.txt")).unwrap();
+        assert_eq!(part1_with(&tree, &points, 10), Ok(40));
+    }
+
+    #[test]
+    fn needs_at_least_two_boxes() {
+        for input in ["", "1,2,3\n"] {
+            assert!(Puzzle::part1(&Day8, input).is_err_and(|e| e.message.contains("need at least 2 junction boxes")), "{input:?}");
+            assert!(Puzzle::part2(&Day8, input).is_err_and(|e| e.message.contains("need at least 2 junction boxes")), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn a_few_boxes_run_out_of_pairs() {
+        // the last pair to join the circuit up is the second closest, and part 1 runs out of pairs long before 1000
+        let input = "1,2,3\n4,5,6\n9,9,9\n";
+        assert_eq!(Puzzle::part2(&Day8, input), Ok(Answer::from(4 * 9)));
+        assert!(Puzzle::part1(&Day8, input).is_err_and(|e| e.message.contains("ran out of pairs")));
+    }
+
+    #[test]
+    fn rejects_coordinates_too_big_to_measure() {
+        assert_eq!(parse_input("1,2,3\n1,99999999999,3\n").err().map(|e| (e.line, e.column)), Some((Some(2), Some(3))));
+    }
+}
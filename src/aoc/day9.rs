@@ -1,8 +1,11 @@
-use crate::Puzzle;
+use crate::{Answer, PuzzleError, Solution};
+use crate::arithmetic;
+use crate::error::column_of;
 
 use itertools::Itertools;
 // Day9 implements day 9 of AoC 2025, as uploaded at https://adventofcode.com/2025/day/9. 
 pub struct Day9;
+register!(9, "Movie Theater", Day9);
 
 // after really overcomplicating yesterday I'm going to keep it simple today. Thanks to Wyn for the inspiration on using itertools for this, that combinations function is lush.
 // I'm not even sure what structure would allow you to do a clever thing here - the area between two points is not a metric (exhibit e.g. a triangle, the area between each pair of points is low other than the hypotenuse)
@@ -15,13 +18,13 @@ pub struct Day9;
 // (I suspect there is going to be something in iterating around the outside of the shape and checking if any tile in the square sits outside of it)
 // nb. the shape will always be convex since we draw a single line. that might be something we can use.
 #[derive(Clone, Copy, Debug)]
-struct RedTile {
+pub struct RedTile {
     x: usize,
     y: usize,
 }
 
 impl RedTile {
-    fn area_separating(self: &RedTile, other: &RedTile) -> usize {
+    fn area_separating(self: &RedTile, other: &RedTile) -> Result<usize, PuzzleError> {
         let (big_x, small_x) = match self.x > other.x {
             true => (self.x, other.x),
             false => (other.x, self.x),
@@ -33,32 +36,184 @@ impl RedTile {
         };
 
         // we need to add one since the rectangle size is non-zero if they're on the same dimension 
-        let area =  (big_x - small_x + 1) * (big_y - small_y + 1);
+        // (the + 1 can only overflow for a tile at usize::MAX, but the area overflows as soon as the coordinates reach 2^32 apart)
+        let what = format_args!("the area between {self:?} and {other:?}");
+        let area = arithmetic::mul(arithmetic::add(big_x - small_x, 1, what)?, arithmetic::add(big_y - small_y, 1, what)?, what)?;
 
-        //println!("area between {self:?} and {other:?} is {area}");
+        crate::trace!("area between {self:?} and {other:?} is {area}");
 
-        return area;
+        return Ok(area);
     }
 }
 
-fn line_to_redtile(line: &str) -> RedTile {
-    let coords = line.trim().split(',').map(|x| x.parse::<usize>().unwrap()).collect::<Vec<usize>>();
+// line_number is only used to say where any error was
+fn line_to_redtile(line: &str, line_number: usize) -> Result<RedTile, PuzzleError> {
+    let coords = line.trim()
+                     .split(',')
+                     .map(|x| x.parse::<usize>().map_err(|e| {
+                         PuzzleError::at(line_number, column_of(line, x), format!("invalid coordinate {x:?}: {e}"))
+                     }))
+                     .collect::<Result<Vec<usize>, PuzzleError>>()?;
     if coords.len() != 2 {
-        panic!("row should have 2 coords, had {}", coords.len());
+        return Err(PuzzleError::at(line_number, 1, format!("row should have 2 coords, had {}", coords.len())));
     }
-    return RedTile { x: coords[0], y: coords[1] }
+    return Ok(RedTile { x: coords[0], y: coords[1] });
 }
 
-impl Puzzle for Day9 {
-    fn part1(&self, input: &String) -> String {
-        return input.lines()
-                    .map(|l| line_to_redtile(l))
+fn parse_input(input: &str) -> Result<Vec<RedTile>, PuzzleError> {
+    return input.lines()
+                .enumerate()
+                .filter(|(_, l)| !l.trim().is_empty())
+                .map(|(i, l)| line_to_redtile(l, i + 1))
+                .collect();
+}
+
+// coming back to part 2: the input is a rectilinear polygon with coordinates up to ~100k, so we can't just paint the whole floor
+// instead we compress the coordinates - only the distinct x and y values of the red tiles matter, plus the (possibly empty) gaps between them
+// each compressed cell is then either a single row/column of real tiles or a whole run of them, and they're all inside or all outside together
+// we paint the polygon's edges onto the compressed grid, flood fill from the border to find everything outside,
+// then a 2D prefix sum over the outside cells lets us check each candidate rectangle in constant time
+struct CompressedGrid {
+    xs: Vec<usize>,
+    ys: Vec<usize>,
+    // outside_before[j][i] is the number of outside cells in rows 0..j and columns 0..i
+    outside_before: Vec<Vec<usize>>,
+}
+
+impl CompressedGrid {
+    fn new(tiles: &[RedTile]) -> Result<CompressedGrid, PuzzleError> {
+        let xs = tiles.iter().map(|t| t.x).sorted().dedup().collect::<Vec<usize>>();
+        let ys = tiles.iter().map(|t| t.y).sorted().dedup().collect::<Vec<usize>>();
+
+        // value i maps to cell 2i+1: odd cells are the coordinates themselves, even cells are the gaps between them
+        // (including a border of gap cells all the way around, which are guaranteed to be outside the polygon)
+        let width = 2 * xs.len() + 1;
+        let height = 2 * ys.len() + 1;
+        let mut edge = vec![vec![false; width]; height];
+
+        // the tiles wrap around, so pair every tile with the next one (and the last with the first)
+        for (a, b) in tiles.iter().zip(tiles.iter().cycle().skip(1)) {
+            let (ax, ay) = (cell(&xs, a.x), cell(&ys, a.y));
+            let (bx, by) = (cell(&xs, b.x), cell(&ys, b.y));
+            if ax != bx && ay != by {
+                return Err(PuzzleError::new(format!("red tiles {a:?} and {b:?} aren't in the same row or column")));
+            }
+            for row in edge.iter_mut().take(ay.max(by) + 1).skip(ay.min(by)) {
+                for c in row.iter_mut().take(ax.max(bx) + 1).skip(ax.min(bx)) {
+                    *c = true;
+                }
+            }
+        }
+
+        // flood fill from the corner, which is always in the border
+        let mut outside = vec![vec![false; width]; height];
+        let mut stack = vec![(0_usize, 0_usize)];
+        outside[0][0] = true;
+        while let Some((x, y)) = stack.pop() {
+            let neighbours = [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)];
+            for (nx, ny) in neighbours {
+                // wrapping_sub means going off the top/left edge gives usize::MAX, which fails this check too
+                if nx >= width || ny >= height || edge[ny][nx] || outside[ny][nx] {
+                    continue;
+                }
+                outside[ny][nx] = true;
+                stack.push((nx, ny));
+            }
+        }
+
+        // a gap between two neighbouring values (e.g. x=5 and x=6) holds no tiles at all, so even though the fill can get through it
+        // (the polygon really is open there), it mustn't count against a rectangle spanning it
+        let has_tiles = |values: &[usize], c: usize| c % 2 == 1 || (c > 0 && c < 2 * values.len() && values[c / 2] - values[c / 2 - 1] > 1);
+
+        let mut outside_before = vec![vec![0; width + 1]; height + 1];
+        for y in 0..height {
+            for x in 0..width {
+                let counts = outside[y][x] && has_tiles(&xs, x) && has_tiles(&ys, y);
+                outside_before[y + 1][x + 1] = outside_before[y][x + 1] + outside_before[y + 1][x] - outside_before[y][x]
+                                             + counts as usize;
+            }
+        }
+
+        return Ok(CompressedGrid { xs, ys, outside_before });
+    }
+
+    // rectangle_inside checks whether every tile in the rectangle with corners a and b is red or green
+    fn rectangle_inside(&self, a: &RedTile, b: &RedTile) -> bool {
+        let (ax, ay) = (cell(&self.xs, a.x), cell(&self.ys, a.y));
+        let (bx, by) = (cell(&self.xs, b.x), cell(&self.ys, b.y));
+        let (x1, x2) = (ax.min(bx), ax.max(bx) + 1);
+        let (y1, y2) = (ay.min(by), ay.max(by) + 1);
+
+        let p = &self.outside_before;
+        return p[y2][x2] + p[y1][x1] - p[y1][x2] - p[y2][x1] == 0;
+    }
+}
+
+// cell maps a coordinate of a red tile to its index in the compressed grid
+fn cell(values: &[usize], v: usize) -> usize {
+    return 2 * values.binary_search(&v).unwrap() + 1;
+}
+
+impl Solution for Day9 {
+    type Parsed<'a> = Vec<RedTile>;
+
+    fn parse(&self, input: &str) -> Result<Vec<RedTile>, PuzzleError> {
+        return parse_input(input);
+    }
+    fn part1(&self, tiles: &Vec<RedTile>) -> Result<Answer, PuzzleError> {
+        return tiles.iter()
+                    .combinations(2)
+                    .map(|v| v[0].area_separating(v[1]))
+                    .process_results(|areas| areas.max())?
+                    .map(Answer::from)
+                    .ok_or_else(|| PuzzleError::new("need at least 2 red tiles"));
+    }
+    fn part2(&self, tiles: &Vec<RedTile>) -> Result<Answer, PuzzleError> {
+        let grid = CompressedGrid::new(tiles)?;
+
+        return tiles.iter()
                     .combinations(2)
-                    .map(|v| v[0].area_separating(&v[1]))
-                    .max().unwrap()
-                    .to_string();
+                    .filter(|v| grid.rectangle_inside(v[0], v[1]))
+                    .map(|v| v[0].area_separating(v[1]))
+                    .process_results(|areas| areas.max())?
+                    .map(Answer::from)
+                    .ok_or_else(|| PuzzleError::new("need at least 2 red tiles"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // an L shape, whose biggest rectangle between red tiles (from (4,0) to (0,4)) goes outside it around (3,3)
+    const L_SHAPE: &str = "0,0\n4,0\n4,2\n2,2\n2,4\n0,4\n";
+
+    #[test]
+    fn parser_skips_blank_lines_and_says_where_bad_tiles_are() {
+        assert_eq!(parse_input("1,2\n\n3,4\n").map(|tiles| tiles.len()), Ok(2));
+        let e = parse_input("1,2\n3,x\n").unwrap_err();
+        assert_eq!((e.line, e.column), (Some(2), Some(3)));
+        let e = parse_input("1,2,3\n").unwrap_err();
+        assert_eq!((e.line, e.message.as_str()), (Some(1), "row should have 2 coords, had 3"));
+    }
+
+    #[test]
+    fn areas_include_both_corners() {
+        assert_eq!(RedTile { x: 2, y: 5 }.area_separating(&RedTile { x: 2, y: 5 }), Ok(1));
+        assert_eq!(RedTile { x: 7, y: 1 }.area_separating(&RedTile { x: 2, y: 3 }), Ok(18));
+    }
+
+    #[test]
+    fn only_part_2_keeps_rectangles_inside() {
+        let tiles = parse_input(L_SHAPE).unwrap();
+        assert_eq!(Solution::part1(&Day9, &tiles), Ok(Answer::from(25)));
+        assert_eq!(Solution::part2(&Day9, &tiles), Ok(Answer::from(15)));
     }
-    fn part2(&self, input: &String) -> String {
-        return input.chars().take(10).collect::<String>();
+
+    #[test]
+    fn needs_two_tiles_in_a_row_or_column() {
+        assert!(Solution::part1(&Day9, &parse_input("1,2\n").unwrap()).is_err());
+        assert!(Solution::part2(&Day9, &parse_input("").unwrap()).is_err());
+        assert!(Solution::part2(&Day9, &parse_input("1,1\n3,3\n").unwrap()).is_err_and(|e| e.message.contains("aren't in the same row or column")));
     }
 }
@@ -0,0 +1,116 @@
+// Dial is a dial numbered 0 to size - 1 (like a safe's), which wraps around from size - 1 to 0 going right and from 0 to size - 1 going left
+// it works out where a rotation ends and how many times it passed 0 from the size of the rotation, rather than turning it a click at a time,
+// so it's exact (and just as quick) however big the rotation is
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dial {
+    size: u64,
+    position: u64,
+}
+
+// Crossings is what happened to the dial during a rotation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Crossings {
+    // how many clicks of the rotation left the dial pointing at 0, including the last one
+    pub zeros: u64,
+    // whether the dial is pointing at 0 once the rotation's done
+    pub ends_at_zero: bool,
+}
+
+impl Dial {
+    // new panics for a dial with no numbers on it, or a position that isn't on the dial
+    pub fn new(size: u64, position: u64) -> Dial {
+        assert!(size > 0, "a dial needs at least one number on it");
+        assert!(position < size, "{position} isn't on a dial of size {size}");
+        return Dial { size, position };
+    }
+
+    pub fn size(&self) -> u64 {
+        return self.size;
+    }
+
+    pub fn position(&self) -> u64 {
+        return self.position;
+    }
+
+    // rotate turns the dial by `clicks`: right (up) if it's positive, and left (down) if it's negative
+    pub fn rotate(&mut self, clicks: i64) -> Crossings {
+        let (size, position, distance) = (self.size as u128, self.position as u128, clicks.unsigned_abs() as u128);
+
+        // going right, the dial is at 0 after every click that takes it to a multiple of size
+        // going left is the same as going right on a dial numbered the other way round, where it starts at size - position (or 0 at 0)
+        // (working in u128 means none of this can overflow, even for a rotation of i64::MIN clicks on a dial of size u64::MAX)
+        let start = match clicks < 0 {
+            false => position,
+            true => (size - position) % size,
+        };
+        let zeros = ((start + distance) / size) as u64;
+
+        // rem_euclid keeps the position on the dial for any rotation, unlike (x+y+100)%100, which goes negative for a left turn of more than 100
+        self.position = (self.position as i128 + clicks as i128).rem_euclid(size as i128) as u64;
+        return Crossings { zeros, ends_at_zero: self.position == 0 };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::random::Random;
+
+    // clicks turns the dial one click at a time, which is obviously right, to check rotate against
+    fn clicks(size: u64, position: u64, clicks: i64) -> (u64, Crossings) {
+        let mut position = position as i64;
+        let mut zeros = 0;
+        for _ in 0..clicks.abs() {
+            position = (position + clicks.signum()).rem_euclid(size as i64);
+            if position == 0 {
+                zeros += 1;
+            }
+        }
+        return (position as u64, Crossings { zeros, ends_at_zero: position == 0 });
+    }
+
+    #[test]
+    fn matches_turning_a_click_at_a_time() {
+        let mut rng = Random::new();
+        let mut random = |n: u64| rng.below(n);
+        for _ in 0..2000 {
+            let size = random(120) + 1;
+            let mut dial = Dial::new(size, random(size));
+            for _ in 0..5 {
+                let rotation = random(1000) as i64 - 500;
+                let start = dial.position();
+                let crossings = dial.rotate(rotation);
+                assert_eq!((dial.position(), crossings), clicks(size, start, rotation), "{rotation} from {start} on a dial of size {size}");
+            }
+        }
+    }
+
+    #[test]
+    fn counts_a_zero_for_each_time_round() {
+        let mut dial = Dial::new(100, 50);
+        assert_eq!(dial.rotate(1000), Crossings { zeros: 10, ends_at_zero: false });
+        // the L200 that (x+y+100)%100 got wrong
+        let mut dial = Dial::new(100, 50);
+        assert_eq!(dial.rotate(-200), Crossings { zeros: 2, ends_at_zero: false });
+        assert_eq!(dial.position(), 50);
+        // starting at 0 doesn't count, but ending there does
+        let mut dial = Dial::new(100, 0);
+        assert_eq!(dial.rotate(-100), Crossings { zeros: 1, ends_at_zero: true });
+        assert_eq!(dial.rotate(0), Crossings { zeros: 0, ends_at_zero: true });
+        // a dial of size 1 is always at 0
+        let mut dial = Dial::new(1, 0);
+        assert_eq!(dial.rotate(-7), Crossings { zeros: 7, ends_at_zero: true });
+    }
+
+    #[test]
+    fn copes_with_the_biggest_rotations() {
+        let mut dial = Dial::new(u64::MAX, u64::MAX - 1);
+        assert_eq!(dial.rotate(i64::MAX), Crossings { zeros: 1, ends_at_zero: false });
+        assert_eq!(dial.position(), i64::MAX as u64 - 1);
+        let mut dial = Dial::new(3, 1);
+        // 2^63 clicks left from 1, which passes 0 after 1 click and then every 3
+        assert_eq!(dial.rotate(i64::MIN), Crossings { zeros: (i64::MIN.unsigned_abs() - 1) / 3 + 1, ends_at_zero: false });
+        // and 2^63 is 2 more than a multiple of 3, so it ends up 2 clicks left of 1
+        assert_eq!(dial.position(), 2);
+    }
+}
@@ -0,0 +1,227 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::PuzzleError;
+
+// Grid is a rectangle of cells, as a lot of puzzle inputs are: each character of the input becomes a cell of whatever type suits the day
+// positions are (x, y), with x the column and y the row (both from 0, from the top left), so grid[(x, y)] reads like a coordinate
+// the cells are stored a row at a time in one Vec, which is both smaller and faster to walk than a Vec per row
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+// the offsets to a cell's neighbours, in reading order
+const NEIGHBOURS_4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const NEIGHBOURS_8: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+impl<T> Grid<T> {
+    // parse makes a grid from the lines of the input, turning each character into a cell with `cell`
+    // a character that `cell` gives None for is an error (at that line and column), as is a line that's a different length to the first
+    // any blank lines at the end (e.g. from an extra newline after the last row) aren't part of the grid
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, PuzzleError> {
+        let input = input.trim_end_matches(['\n', '\r']);
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+        for (y, line) in input.lines().enumerate() {
+            let before = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                match cell(c) {
+                    Some(v) => cells.push(v),
+                    None => return Err(PuzzleError::at(y + 1, x + 1, format!("unexpected {c:?} in the grid"))),
+                }
+            }
+            let line_width = cells.len() - before;
+            match width {
+                None => width = Some(line_width),
+                Some(w) if w != line_width => {
+                    return Err(PuzzleError::at(y + 1, w.min(line_width) + 1, format!("line is {line_width} wide, but the first line is {w} wide")));
+                },
+                Some(_) => {},
+            }
+            height += 1;
+        }
+        return Ok(Grid { width: width.unwrap_or(0), height, cells });
+    }
+
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    // get is the cell at (x, y), or None if that's off the grid
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        return Some(&self.cells[y * self.width + x]);
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        return Some(&mut self.cells[y * self.width + x]);
+    }
+
+    // positions is every (x, y) on the grid, in reading order (a row at a time, left to right)
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let width = self.width;
+        return (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)));
+    }
+
+    // iter is every cell along with its position, in reading order
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        return self.positions().zip(&self.cells);
+    }
+
+    // neighbours4 is the positions up, left, right and down of (x, y), leaving out any that are off the grid
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + use<'_, T> {
+        return self.offsets(x, y, &NEIGHBOURS_4);
+    }
+
+    // neighbours8 is the positions all around (x, y), including diagonally, leaving out any that are off the grid
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + use<'_, T> {
+        return self.offsets(x, y, &NEIGHBOURS_8);
+    }
+
+    fn offsets(&self, x: usize, y: usize, offsets: &'static [(isize, isize)]) -> impl Iterator<Item = (usize, usize)> + use<'_, T> {
+        return offsets.iter().filter_map(move |&(dx, dy)| {
+            let (nx, ny) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+            return (nx < self.width && ny < self.height).then_some((nx, ny));
+        });
+    }
+
+    // row is the cells of row y, left to right
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y >= self.height {
+            return None;
+        }
+        return Some(&self.cells[y * self.width..(y + 1) * self.width]);
+    }
+
+    // rows is every row in turn, from the top
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // (chunks_exact doesn't allow a width of 0, which an empty grid has)
+        return (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width]);
+    }
+
+    // column is the cells of column x, top to bottom (which is empty if x is off the grid)
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let rows = if x < self.width { self.height } else { 0 };
+        return (0..rows).map(move |y| &self.cells[y * self.width + x]);
+    }
+
+    // columns is every column in turn, from the left
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        return (0..self.width).map(|x| self.column(x));
+    }
+
+    // map makes a grid of the same shape with each cell turned into something else, e.g. a char to print
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        return Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() };
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    // find is the position of the first cell (in reading order) that's equal to `cell`, e.g. where the start is
+    pub fn find(&self, cell: &T) -> Option<(usize, usize)> {
+        return self.iter().find(|(_, c)| *c == cell).map(|(position, _)| position);
+    }
+}
+
+// indexing panics if the position is off the grid, like a slice does; get is the alternative for positions that might be
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        return self.get(x, y).unwrap_or_else(|| panic!("({x}, {y}) is off the {}x{} grid", self.width, self.height));
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        return self.get_mut(x, y).unwrap_or_else(|| panic!("({x}, {y}) is off the {width}x{height} grid"));
+    }
+}
+
+// a grid is written back out a row per line, so a grid of cells that display as their character prints just like the input
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Grid<u32> {
+        return Grid::parse(input, |c| c.to_digit(10)).unwrap();
+    }
+
+    #[test]
+    fn parses_and_displays_the_same_text() {
+        let grid = digits("123\n456\n");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+        assert_eq!(grid.to_string(), "123\n456");
+        assert_eq!(grid.find(&5), Some((1, 1)));
+        assert_eq!(grid.find(&7), None);
+    }
+
+    #[test]
+    fn rejects_bad_characters_and_ragged_lines() {
+        let e = Grid::parse("12\n3x\n", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((e.line, e.column), (Some(2), Some(2)));
+        let e = Grid::parse("123\n45\n", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((e.line, e.column), (Some(2), Some(3)));
+        assert_eq!(Grid::parse("", |c| c.to_digit(10)).unwrap().height(), 0);
+        // but a blank line in the middle is still a ragged line
+        let e = Grid::parse("12\n\n34\n", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((e.line, e.column), (Some(2), Some(1)));
+    }
+
+    #[test]
+    fn ignores_blank_lines_at_the_end() {
+        let grid = digits("12\n34\n\n\r\n");
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid.to_string(), "12\n34");
+    }
+
+    #[test]
+    fn neighbours_stay_on_the_grid() {
+        let grid = digits("123\n456\n789");
+        assert_eq!(grid.neighbours8(1, 1).count(), 8);
+        assert_eq!(grid.neighbours8(0, 0).collect::<Vec<_>>(), [(1, 0), (0, 1), (1, 1)]);
+        assert_eq!(grid.neighbours4(1, 1).map(|(x, y)| grid[(x, y)]).collect::<Vec<_>>(), [2, 4, 6, 8]);
+        assert_eq!(grid.neighbours4(2, 2).collect::<Vec<_>>(), [(2, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = digits("12\n34\n56");
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[1, 2], [3, 4], [5, 6]]);
+        assert_eq!(grid.row(3), None);
+        assert_eq!(grid.columns().map(|c| c.copied().collect::<Vec<_>>()).collect::<Vec<_>>(), [vec![1, 3, 5], vec![2, 4, 6]]);
+        assert_eq!(grid.column(2).count(), 0);
+        assert_eq!(grid.map(|v| v * 10).iter().last(), Some(((1, 2), &60)));
+    }
+}
@@ -0,0 +1,302 @@
+use std::fmt;
+
+// IntervalSet is a set of integers stored as the ranges they make up, for when there are far too many to list one by one
+// the ranges are inclusive at both ends (as puzzle inputs usually give them, e.g. 3-5 is 3, 4 and 5), and are kept sorted, with
+// any that overlap or touch merged together, so that every set has exactly one representation
+// that makes membership a binary search, and union, intersection and difference a single sweep along both sets
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<(T, T)>,
+}
+
+// Discrete is what an IntervalSet can hold: integers, which have a next and previous value, so that e.g. 1-2 and 3-4 can be merged
+pub trait Discrete: Copy + Ord {
+    fn next(self) -> Option<Self>;
+    fn prev(self) -> Option<Self>;
+    // count is how many values there are from start to end (inclusive), which for 64 bit types can need a u128
+    fn count(start: Self, end: Self) -> u128;
+}
+
+macro_rules! discrete {
+    ($($t:ty),*) => {
+        $(
+            impl Discrete for $t {
+                fn next(self) -> Option<$t> {
+                    return self.checked_add(1);
+                }
+                fn prev(self) -> Option<$t> {
+                    return self.checked_sub(1);
+                }
+                fn count(start: $t, end: $t) -> u128 {
+                    return (end as i128 - start as i128) as u128 + 1;
+                }
+            }
+        )*
+    };
+}
+
+discrete!(i32, i64, isize, u32, u64, usize);
+
+// touches is whether the interval ending at `end` overlaps or is right next to a later one starting at `start`, so they'd merge
+fn touches<T: Discrete>(end: T, start: T) -> bool {
+    return start <= end || end.next() == Some(start);
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        return IntervalSet { intervals: Vec::new() };
+    }
+
+    // from_sorted merges intervals that are already sorted by start (which is what the sweeps below produce)
+    fn from_sorted(sorted: impl IntoIterator<Item = (T, T)>) -> IntervalSet<T> {
+        let mut intervals: Vec<(T, T)> = Vec::new();
+        for (start, end) in sorted {
+            match intervals.last_mut() {
+                Some(last) if touches(last.1, start) => last.1 = last.1.max(end),
+                _ => intervals.push((start, end)),
+            }
+        }
+        return IntervalSet { intervals };
+    }
+
+    // insert adds every value from start to end (inclusive), merging with any intervals that it overlaps or touches
+    // an interval that ends before it starts is empty, so that adds nothing
+    pub fn insert(&mut self, start: T, end: T) {
+        if end < start {
+            return;
+        }
+        // the intervals that merge with the new one are a contiguous run: after everything that ends (with a gap) before it starts,
+        // and before everything that starts (with a gap) after it ends
+        let first = self.intervals.partition_point(|&(_, e)| !touches(e, start));
+        let last = self.intervals.partition_point(|&(s, _)| touches(end, s));
+        let merged = match first < last {
+            true => (start.min(self.intervals[first].0), end.max(self.intervals[last - 1].1)),
+            false => (start, end),
+        };
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.intervals.partition_point(|&(_, e)| e < value);
+        return self.intervals.get(i).is_some_and(|&(s, _)| s <= value);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.intervals.is_empty();
+    }
+
+    // covered is how many values are in the set
+    pub fn covered(&self) -> u128 {
+        return self.intervals.iter().map(|&(s, e)| T::count(s, e)).sum();
+    }
+
+    // iter is the (merged) intervals, in order
+    pub fn iter(&self) -> impl Iterator<Item = (T, T)> + '_ {
+        return self.intervals.iter().copied();
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        // both are sorted, so merging them in order of start is all it takes
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+        let sorted = std::iter::from_fn(|| match (a.peek(), b.peek()) {
+            (Some(x), Some(y)) if y.0 < x.0 => b.next(),
+            (Some(_), _) => a.next(),
+            (None, _) => b.next(),
+        });
+        return IntervalSet::from_sorted(sorted);
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+            let (start, end) = (a.0.max(b.0), a.1.min(b.1));
+            if start <= end {
+                intervals.push((start, end));
+            }
+            // whichever ends first can't overlap anything else in the other set
+            match a.1 < b.1 {
+                true => i += 1,
+                false => j += 1,
+            }
+        }
+        // the pieces are in order, and can't touch: two neighbouring values that are in both sets are in the same interval of each
+        return IntervalSet { intervals };
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = Vec::new();
+        let mut j = 0;
+        for &(start, end) in &self.intervals {
+            // `from` is where the rest of this interval starts, once the parts of other before it have been cut out
+            let mut from = Some(start);
+            // skip past everything in other that ends before this interval starts
+            while j < other.intervals.len() && other.intervals[j].1 < start {
+                j += 1;
+            }
+            let mut k = j;
+            while let Some(f) = from {
+                match other.intervals.get(k) {
+                    Some(&(s, e)) if s <= end => {
+                        if f < s {
+                            // s > f, so it has a previous value
+                            intervals.push((f, s.prev().unwrap()));
+                        }
+                        from = e.next().filter(|&n| n <= end).map(|n| n.max(f));
+                        k += 1;
+                    },
+                    _ => {
+                        intervals.push((f, end));
+                        from = None;
+                    },
+                }
+            }
+            // the last interval of other that was looked at can carry on into the next interval of self
+            j = k.saturating_sub(1).max(j);
+        }
+        return IntervalSet { intervals };
+    }
+}
+
+impl<T: Discrete> Default for IntervalSet<T> {
+    fn default() -> IntervalSet<T> {
+        return IntervalSet::new();
+    }
+}
+
+impl<T: Discrete> FromIterator<(T, T)> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> IntervalSet<T> {
+        let mut set = IntervalSet::new();
+        for (start, end) in iter {
+            set.insert(start, end);
+        }
+        return set;
+    }
+}
+
+// e.g. {3-5, 10-20}
+impl<T: fmt::Debug> fmt::Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let intervals = self.intervals.iter().map(|(s, e)| format!("{s:?}-{e:?}")).collect::<Vec<_>>();
+        return write!(f, "{{{}}}", intervals.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::random::Random;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet<i64> {
+        return intervals.iter().copied().collect();
+    }
+
+    fn overlap(x: (i64, i64), y: (i64, i64)) -> bool {
+        return !set(&[x]).intersection(&set(&[y])).is_empty();
+    }
+
+    #[test]
+    fn overlap_detects_every_arrangement() {
+        // partial overlaps either way round
+        assert!(overlap((1, 5), (3, 8)));
+        assert!(overlap((3, 8), (1, 5)));
+        // one range inside the other, either way round
+        assert!(overlap((1, 10), (3, 4)));
+        assert!(overlap((3, 4), (1, 10)));
+        // sharing just an endpoint still counts, since ranges are inclusive
+        assert!(overlap((1, 3), (3, 5)));
+        assert!(overlap((5, 5), (5, 5)));
+    }
+
+    #[test]
+    fn overlap_rejects_disjoint_ranges() {
+        assert!(!overlap((1, 2), (4, 5)));
+        assert!(!overlap((4, 5), (1, 2)));
+        // next to each other isn't overlapping, even though they'd merge into one interval
+        assert!(!overlap((1, 2), (3, 4)));
+    }
+
+    #[test]
+    fn insert_keeps_intervals_sorted() {
+        let ranges = set(&[(10, 14), (3, 5), (20, 30)]);
+        assert_eq!(ranges.iter().collect::<Vec<_>>(), [(3, 5), (10, 14), (20, 30)]);
+    }
+
+    #[test]
+    fn insert_merges_overlapping_intervals() {
+        // the fresh ranges from day 5's example
+        let ranges = set(&[(3, 5), (10, 14), (16, 20), (12, 18)]);
+        assert_eq!(ranges.iter().collect::<Vec<_>>(), [(3, 5), (10, 20)]);
+        assert_eq!(ranges.covered(), 14);
+    }
+
+    #[test]
+    fn insert_merges_an_interval_bridging_several() {
+        let mut ranges = set(&[(1, 2), (4, 5), (7, 8)]);
+        ranges.insert(2, 7);
+        assert_eq!(ranges.iter().collect::<Vec<_>>(), [(1, 8)]);
+    }
+
+    #[test]
+    fn insert_merges_touching_intervals() {
+        let mut ranges = set(&[(1, 2), (4, 5)]);
+        ranges.insert(3, 3);
+        assert_eq!(ranges, set(&[(1, 5)]));
+        // an empty interval adds nothing
+        ranges.insert(9, 8);
+        assert_eq!(ranges, set(&[(1, 5)]));
+    }
+
+    #[test]
+    fn contains_is_inclusive() {
+        let ranges = set(&[(3, 5), (10, 20)]);
+        for (value, expected) in [(2, false), (3, true), (5, true), (6, false), (10, true), (20, true), (21, false)] {
+            assert_eq!(ranges.contains(value), expected, "{value}");
+        }
+        assert!(!IntervalSet::<i64>::new().contains(0));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[(1, 10), (20, 30)]);
+        let b = set(&[(5, 22), (28, 40)]);
+        assert_eq!(a.union(&b), set(&[(1, 40)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 22), (28, 30)]));
+        assert_eq!(a.difference(&b), set(&[(1, 4), (23, 27)]));
+        assert_eq!(b.difference(&a), set(&[(11, 19), (31, 40)]));
+        assert_eq!(a.difference(&a), IntervalSet::new());
+        assert_eq!(a.union(&IntervalSet::new()), a);
+    }
+
+    // the set operations should agree with doing the same thing a value at a time
+    #[test]
+    fn set_operations_match_brute_force() {
+        let mut rng = Random::new();
+        let mut random = |n: u64| rng.below(n) as i64;
+        for _ in 0..200 {
+            let mut random_set = || (0..random(5)).map(|_| { let s = random(40); (s, s + random(8)) }).collect::<IntervalSet<i64>>();
+            let (a, b) = (random_set(), random_set());
+            let values = |s: &IntervalSet<i64>| (-1..50).filter(|&v| s.contains(v)).collect::<Vec<_>>();
+            let brute = |keep: fn(bool, bool) -> bool| (-1..50).filter(|&v| keep(a.contains(v), b.contains(v))).collect::<Vec<_>>();
+
+            assert_eq!(values(&a.union(&b)), brute(|x, y| x || y), "{a:?} | {b:?}");
+            assert_eq!(values(&a.intersection(&b)), brute(|x, y| x && y), "{a:?} & {b:?}");
+            assert_eq!(values(&a.difference(&b)), brute(|x, y| x && !y), "{a:?} - {b:?}");
+            assert_eq!(a.union(&b).covered() as usize, brute(|x, y| x || y).len());
+            // and each result is merged properly, so it's equal to the same set built up one value at a time
+            assert_eq!(a.difference(&b), brute(|x, y| x && !y).into_iter().map(|v| (v, v)).collect());
+        }
+    }
+
+    #[test]
+    fn handles_the_ends_of_the_type() {
+        let mut ranges = IntervalSet::<u64>::new();
+        ranges.insert(u64::MAX - 1, u64::MAX);
+        ranges.insert(0, 0);
+        assert_eq!(ranges.covered(), 3);
+        ranges.insert(0, u64::MAX);
+        assert_eq!(ranges.covered(), u64::MAX as u128 + 1);
+        assert!(ranges.difference(&ranges).is_empty());
+    }
+}
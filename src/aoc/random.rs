@@ -0,0 +1,17 @@
+// Random is a small xorshift, for tests that check something against brute force on lots of made up cases
+// it means those tests don't need a random number crate, and always check the same cases
+pub(crate) struct Random(u64);
+
+impl Random {
+    pub(crate) fn new() -> Random {
+        return Random(0x2545f4914f6cdd1d);
+    }
+
+    // below is a number from 0 to n - 1
+    pub(crate) fn below(&mut self, n: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        return self.0 % n;
+    }
+}
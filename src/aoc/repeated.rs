@@ -0,0 +1,241 @@
+use std::ops::RangeInclusive;
+
+use itertools::Itertools;
+
+use crate::PuzzleError;
+use crate::arithmetic;
+
+// numbers made of a block of digits repeated some number of times, e.g. 123123 (123 twice) or 7777 (7 four times, or 77 twice)
+// these come up in puzzles like day 2, which wants every such number in some ranges: checking each number in a range one at a time
+// is far too slow for big ranges, but the ones that are k copies of a block are the block times a multiplier (e.g. 123123 = 123 * 1001),
+// so they can be listed (or summed, as an arithmetic series) straight from the ends of the range
+//
+// a number counts if it can be written as k copies of a block for any k that Repeats allows, so e.g. 7777 counts for exactly 2 repeats,
+// exactly 4, or at least 3, but not exactly 3; and the number of digits is in whatever radix is in use
+
+// Repeats is which numbers of repeats count
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Repeats {
+    Exactly(u32),
+    AtLeast(u32),
+    Primes,
+    OneOf(Vec<u32>),
+}
+
+impl Repeats {
+    pub fn allows(&self, repeats: u32) -> bool {
+        return match self {
+            Repeats::Exactly(k) => repeats == *k,
+            Repeats::AtLeast(k) => repeats >= *k,
+            Repeats::Primes => repeats >= 2 && (2..repeats).all(|d| !repeats.is_multiple_of(d)),
+            Repeats::OneOf(ks) => ks.contains(&repeats),
+        };
+    }
+}
+
+// RepeatedBlocks is the numbers, in some radix, that are an allowed number of repeats of a block of digits
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RepeatedBlocks {
+    radix: u32,
+    repeats: Repeats,
+}
+
+fn divisors(n: u32) -> impl Iterator<Item = u32> {
+    return (1..=n).filter(move |d| n.is_multiple_of(*d));
+}
+
+// mobius is the Möbius function: 0 if n has a square factor, otherwise -1 or 1 for an odd or even number of prime factors
+fn mobius(mut n: u32) -> i32 {
+    let mut mu = 1;
+    let mut p = 2;
+    while n > 1 {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
+            }
+            mu = -mu;
+        }
+        p += 1;
+    }
+    return mu;
+}
+
+impl RepeatedBlocks {
+    // new panics for a radix of less than 2 (or more than 36, which is as far as digits go), like u64::from_str_radix does
+    pub fn new(radix: u32, repeats: Repeats) -> RepeatedBlocks {
+        assert!((2..=36).contains(&radix), "radix must be from 2 to 36, not {radix}");
+        return RepeatedBlocks { radix, repeats };
+    }
+
+    fn power(&self, digits: u32) -> u128 {
+        return (self.radix as u128).pow(digits);
+    }
+
+    // lengths are the numbers of digits that numbers in lo..=hi can have, along with the part of the range with that many digits
+    fn lengths(&self, lo: u64, hi: u64) -> impl Iterator<Item = (u32, u128, u128)> + '_ {
+        let (lo, hi) = (lo as u128, hi as u128);
+        return (1..)
+            .take_while(move |&digits| self.power(digits - 1) <= hi)
+            .map(move |digits| (digits, lo.max(self.power(digits - 1)), hi.min(self.power(digits) - 1)))
+            .filter(|(_, lo, hi)| lo <= hi);
+    }
+
+    // blocks is the multiplier for `repeats` copies of a block in `digits` digits, and the first and last blocks that land in lo..=hi
+    // e.g. for 2 copies in 6 digits that's 1001, and the blocks are from 100 to 999 (less any that are outside the range)
+    fn blocks(&self, lo: u128, hi: u128, digits: u32, repeats: u32) -> (u128, u128, u128) {
+        let block_digits = digits / repeats;
+        let multiplier = (self.power(digits) - 1) / (self.power(block_digits) - 1);
+        // the block can't start with a 0, since then the number would have fewer digits
+        let first = lo.div_ceil(multiplier).max(self.power(block_digits - 1));
+        let last = (hi / multiplier).min(self.power(block_digits) - 1);
+        return (multiplier, first, last);
+    }
+
+    // copies_sum is the sum of the numbers in lo..=hi (which all have `digits` digits) that are `repeats` copies of a block
+    fn copies_sum(&self, lo: u128, hi: u128, digits: u32, repeats: u32) -> Result<u128, PuzzleError> {
+        let (multiplier, first, last) = self.blocks(lo, hi, digits, repeats);
+        if first > last {
+            return Ok(0);
+        }
+        // the blocks are an arithmetic series, so they add up to their count times their average
+        // one of the count and first + last is always even, and halving that first keeps this inside a u128 for any u64 range
+        let count = last - first + 1;
+        let blocks = match count % 2 {
+            0 => (count / 2) * (first + last),
+            _ => count * ((first + last) / 2),
+        };
+        crate::trace!("{repeats} copies of blocks {first} to {last} between {lo} and {hi} add up to {multiplier} * {blocks}");
+        return arithmetic::mul(multiplier, blocks, "the sum of the repeated numbers in a range");
+    }
+
+    // counts is whether a number whose shortest block repeats `most` times counts, i.e. whether any of the numbers of copies it can
+    // be split into (which are the divisors of `most`, e.g. 777777 is 6 copies of 7, so it's also 1, 2 or 3 copies) are allowed
+    fn counts(&self, most: u32) -> bool {
+        return divisors(most).any(|k| self.repeats.allows(k));
+    }
+
+    // sum is the sum of the numbers in the range that are an allowed number of repeats
+    //
+    // copies_sum gives the sum of the numbers that are k copies, but a number that's k copies is also k copies for any k dividing its
+    // most copies, so adding those up would count it more than once
+    // instead Möbius inversion gives the sum of the numbers whose most copies is exactly m, from the sums of the numbers that are j copies
+    // for every multiple j of m: those that are m copies, less those that are more than m copies (with the right inclusion-exclusion)
+    pub fn sum(&self, range: RangeInclusive<u64>) -> Result<u128, PuzzleError> {
+        let what = "the sum of the repeated numbers in a range";
+        let (mut added, mut taken) = (0_u128, 0_u128);
+        for (digits, lo, hi) in self.lengths(*range.start(), *range.end()) {
+            for most in divisors(digits).filter(|&m| self.counts(m)) {
+                for j in divisors(digits).filter(|j| j.is_multiple_of(most)) {
+                    match mobius(j / most) {
+                        1 => added = arithmetic::add(added, self.copies_sum(lo, hi, digits, j)?, what)?,
+                        -1 => taken = arithmetic::add(taken, self.copies_sum(lo, hi, digits, j)?, what)?,
+                        _ => {},
+                    }
+                }
+            }
+        }
+        // each term of the inversion takes away numbers that were added in by another term, so this can't go below 0
+        return arithmetic::sub(added, taken, what);
+    }
+
+    // iter lists the numbers in the range that are an allowed number of repeats, in order
+    // it works out each one from the blocks, so it only takes as long as there are numbers to list (however big the range is)
+    pub fn iter(&self, range: RangeInclusive<u64>) -> impl Iterator<Item = u64> + '_ {
+        return self.lengths(*range.start(), *range.end()).flat_map(move |(digits, lo, hi)| {
+            // every number is k copies for some k that's allowed, so merging each allowed k's numbers (which are already in order) lists them all,
+            // and any that are more than one allowed k come out next to each other, so dedup drops the repeats
+            divisors(digits)
+                .filter(|&k| self.repeats.allows(k))
+                .map(|k| {
+                    let (multiplier, first, last) = self.blocks(lo, hi, digits, k);
+                    // everything in lo..=hi fits in a u64, so these do
+                    return (first..=last).map(move |block| (block * multiplier) as u64);
+                })
+                .kmerge()
+                .dedup()
+        });
+    }
+
+    // is_repeated checks a single number, by looking at its digits, which is what sum and iter are tested against
+    pub fn is_repeated(&self, n: u64) -> bool {
+        let mut digits = Vec::new();
+        let mut rest = n;
+        while rest > 0 {
+            digits.push(rest % self.radix as u64);
+            rest /= self.radix as u64;
+        }
+        let length = digits.len() as u32;
+        return divisors(length)
+            .filter(|&k| self.repeats.allows(k))
+            .any(|k| {
+                let block = (length / k) as usize;
+                return digits.chunks(block).all(|chunk| chunk == &digits[..block]);
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(blocks: &RepeatedBlocks, range: RangeInclusive<u64>) -> Vec<u64> {
+        return range.filter(|&n| blocks.is_repeated(n)).collect();
+    }
+
+    #[test]
+    fn matches_checking_every_number() {
+        let configurations = [Repeats::Exactly(2), Repeats::AtLeast(2), Repeats::Exactly(3), Repeats::Primes, Repeats::OneOf(vec![3, 4]), Repeats::AtLeast(1)];
+        for radix in [2, 10, 16] {
+            for repeats in configurations.iter().cloned() {
+                let blocks = RepeatedBlocks::new(radix, repeats);
+                for range in [0..=70_000, 95..=115, 123_000..=124_000, 5..=5] {
+                    let expected = brute_force(&blocks, range.clone());
+                    assert_eq!(blocks.iter(range.clone()).collect::<Vec<_>>(), expected, "{blocks:?} in {range:?}");
+                    assert_eq!(blocks.sum(range.clone()), Ok(expected.iter().map(|&n| n as u128).sum()), "{blocks:?} in {range:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn counts_each_number_once() {
+        // 111111 is 2, 3 and 6 copies of a block, but is only one number
+        let blocks = RepeatedBlocks::new(10, Repeats::AtLeast(2));
+        assert_eq!(blocks.iter(111111..=111111).collect::<Vec<_>>(), [111111]);
+        assert_eq!(blocks.sum(1111111111..=1111111111), Ok(1111111111));
+        // 121212 is 3 copies of 12, but it isn't 2 copies of anything
+        assert!(RepeatedBlocks::new(10, Repeats::Exactly(3)).is_repeated(121212));
+        assert!(!RepeatedBlocks::new(10, Repeats::Exactly(2)).is_repeated(121212));
+    }
+
+    #[test]
+    fn works_in_other_radixes() {
+        // 0xabab is 0xab twice
+        let hex = RepeatedBlocks::new(16, Repeats::Exactly(2));
+        assert_eq!(hex.iter(0xab00..=0xacff).collect::<Vec<_>>(), [0xabab, 0xacac]);
+        // 0b1010 (10) and 0b1111 (15) are the only 4 bit numbers that are 2 copies
+        assert_eq!(RepeatedBlocks::new(2, Repeats::Exactly(2)).iter(8..=15).collect::<Vec<_>>(), [10, 15]);
+    }
+
+    #[test]
+    fn copes_with_the_whole_range_of_u64() {
+        for radix in [2, 10, 16, 36] {
+            let blocks = RepeatedBlocks::new(radix, Repeats::AtLeast(2));
+            assert!(blocks.sum(0..=u64::MAX).is_ok());
+            // the iterator can start anywhere without listing everything before it
+            assert_eq!(blocks.iter(u64::MAX - 1000..=u64::MAX).count(), brute_force(&blocks, u64::MAX - 1000..=u64::MAX).len());
+        }
+        // every number is 1 copy, so that's every number there is
+        assert_eq!(RepeatedBlocks::new(10, Repeats::AtLeast(1)).sum(0..=u64::MAX), Ok(u64::MAX as u128 * (u64::MAX as u128 + 1) / 2));
+    }
+
+    #[test]
+    fn number_theory() {
+        assert_eq!(divisors(12).collect::<Vec<_>>(), [1, 2, 3, 4, 6, 12]);
+        assert_eq!([1, 2, 3, 4, 5, 6, 12, 30].map(mobius), [1, -1, -1, 0, -1, 1, 0, -1]);
+        assert!(Repeats::Primes.allows(7));
+        assert!(!Repeats::Primes.allows(9));
+        assert!(!Repeats::Primes.allows(1));
+    }
+}
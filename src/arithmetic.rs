@@ -0,0 +1,206 @@
+use std::any::type_name;
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::PuzzleError;
+
+// the solvers do their sums in fixed-size integers, which in a release build silently wrap around if an answer gets too big
+// a global Mode (like the log level) says what to do about that instead, and the solvers do any arithmetic that could overflow
+// through add/sub/mul/pow/sum/product here, which check for it if the mode says to
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Mode {
+    // plain Rust arithmetic: overflow panics in a debug build, and wraps around in a release build
+    Unchecked = 0,
+    // overflow is a PuzzleError saying what overflowed
+    Checked = 1,
+    // as Checked, but solvers that can (currently only day 6) work in arbitrary precision, so that they get the right answer however big it is
+    Big = 2,
+}
+
+static MODE: AtomicU8 = AtomicU8::new(Mode::Unchecked as u8);
+
+pub fn set_mode(mode: Mode) {
+    MODE.store(mode as u8, Ordering::Relaxed);
+}
+
+pub fn mode() -> Mode {
+    return match MODE.load(Ordering::Relaxed) {
+        0 => Mode::Unchecked,
+        1 => Mode::Checked,
+        _ => Mode::Big,
+    };
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f.pad(match self {
+            Mode::Unchecked => "unchecked",
+            Mode::Checked => "checked",
+            Mode::Big => "big",
+        });
+    }
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Mode, String> {
+        return match s {
+            "unchecked" => Ok(Mode::Unchecked),
+            "checked" => Ok(Mode::Checked),
+            "big" => Ok(Mode::Big),
+            _ => Err(format!("arithmetic must be one of unchecked, checked or big, not {s:?}")),
+        };
+    }
+}
+
+// Integer is any of the primitive integer types, so that the checked operations work on whichever one a solver uses
+pub trait Integer: Copy + fmt::Display + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn checked_pow(self, exp: u32) -> Option<Self>;
+    fn pow(self, exp: u32) -> Self;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: $t = 0;
+                const ONE: $t = 1;
+                fn checked_add(self, other: $t) -> Option<$t> {
+                    return <$t>::checked_add(self, other);
+                }
+                fn checked_sub(self, other: $t) -> Option<$t> {
+                    return <$t>::checked_sub(self, other);
+                }
+                fn checked_mul(self, other: $t) -> Option<$t> {
+                    return <$t>::checked_mul(self, other);
+                }
+                fn checked_pow(self, exp: u32) -> Option<$t> {
+                    return <$t>::checked_pow(self, exp);
+                }
+                fn pow(self, exp: u32) -> $t {
+                    return <$t>::pow(self, exp);
+                }
+            }
+        )*
+    };
+}
+
+integer!(i32, i64, i128, isize, u32, u64, u128, usize);
+
+// `what` says which calculation this is part of, e.g. "the area of a rectangle", so that the error says what overflowed
+// it's only formatted if there is an error, so format_args! is a cheap way of including details like which problem it was
+fn overflow<T: Integer>(what: impl fmt::Display, a: T, op: &str, b: impl fmt::Display) -> PuzzleError {
+    return PuzzleError::new(format!("overflow in {what}: {a} {op} {b} doesn't fit in a {}", type_name::<T>()));
+}
+
+impl Mode {
+    fn checked(self) -> bool {
+        return self != Mode::Unchecked;
+    }
+
+    pub fn add<T: Integer>(self, a: T, b: T, what: impl fmt::Display) -> Result<T, PuzzleError> {
+        if !self.checked() {
+            return Ok(a + b);
+        }
+        return a.checked_add(b).ok_or_else(|| overflow(what, a, "+", b));
+    }
+
+    pub fn sub<T: Integer>(self, a: T, b: T, what: impl fmt::Display) -> Result<T, PuzzleError> {
+        if !self.checked() {
+            return Ok(a - b);
+        }
+        return a.checked_sub(b).ok_or_else(|| overflow(what, a, "-", b));
+    }
+
+    pub fn mul<T: Integer>(self, a: T, b: T, what: impl fmt::Display) -> Result<T, PuzzleError> {
+        if !self.checked() {
+            return Ok(a * b);
+        }
+        return a.checked_mul(b).ok_or_else(|| overflow(what, a, "*", b));
+    }
+
+    pub fn pow<T: Integer>(self, base: T, exp: u32, what: impl fmt::Display) -> Result<T, PuzzleError> {
+        if !self.checked() {
+            return Ok(base.pow(exp));
+        }
+        return base.checked_pow(exp).ok_or_else(|| overflow(what, base, "^", exp));
+    }
+
+    pub fn sum<T: Integer>(self, values: impl IntoIterator<Item = T>, what: impl fmt::Display + Copy) -> Result<T, PuzzleError> {
+        return values.into_iter().try_fold(T::ZERO, |acc, v| self.add(acc, v, what));
+    }
+
+    pub fn product<T: Integer>(self, values: impl IntoIterator<Item = T>, what: impl fmt::Display + Copy) -> Result<T, PuzzleError> {
+        return values.into_iter().try_fold(T::ONE, |acc, v| self.mul(acc, v, what));
+    }
+}
+
+// these do the same in the global mode, which is what the solvers use
+pub fn add<T: Integer>(a: T, b: T, what: impl fmt::Display) -> Result<T, PuzzleError> {
+    return mode().add(a, b, what);
+}
+
+pub fn sub<T: Integer>(a: T, b: T, what: impl fmt::Display) -> Result<T, PuzzleError> {
+    return mode().sub(a, b, what);
+}
+
+pub fn mul<T: Integer>(a: T, b: T, what: impl fmt::Display) -> Result<T, PuzzleError> {
+    return mode().mul(a, b, what);
+}
+
+pub fn pow<T: Integer>(base: T, exp: u32, what: impl fmt::Display) -> Result<T, PuzzleError> {
+    return mode().pow(base, exp, what);
+}
+
+pub fn sum<T: Integer>(values: impl IntoIterator<Item = T>, what: impl fmt::Display + Copy) -> Result<T, PuzzleError> {
+    return mode().sum(values, what);
+}
+
+pub fn product<T: Integer>(values: impl IntoIterator<Item = T>, what: impl fmt::Display + Copy) -> Result<T, PuzzleError> {
+    return mode().product(values, what);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the tests use a Mode directly rather than the global one, since other tests running at the same time could change that
+
+    #[test]
+    fn checked_overflow_is_an_error_naming_the_operation() {
+        let e = Mode::Checked.mul(u64::MAX, 2, "the area of a rectangle").unwrap_err();
+        assert_eq!(e.message, "overflow in the area of a rectangle: 18446744073709551615 * 2 doesn't fit in a u64");
+        let e = Mode::Big.product([1_usize << 40, 1 << 40], format_args!("problem {}", 3)).unwrap_err();
+        assert!(e.message.starts_with("overflow in problem 3: 1099511627776 * 1099511627776"));
+        assert!(Mode::Checked.sub(1_u32, 2, "a gap").is_err());
+        assert!(Mode::Checked.pow(10_u64, 20, "a power of ten").is_err());
+    }
+
+    #[test]
+    fn no_overflow_is_the_same_in_every_mode() {
+        for mode in [Mode::Unchecked, Mode::Checked, Mode::Big] {
+            assert_eq!(mode.add(2_i64, 3, ""), Ok(5));
+            assert_eq!(mode.sub(2_i64, 3, ""), Ok(-1));
+            assert_eq!(mode.pow(10_u64, 19, ""), Ok(10_000_000_000_000_000_000));
+            assert_eq!(mode.sum([1_usize, 2, 3], ""), Ok(6));
+            assert_eq!(mode.product(Vec::<usize>::new(), ""), Ok(1));
+        }
+    }
+
+    #[test]
+    fn parses_modes() {
+        for mode in [Mode::Unchecked, Mode::Checked, Mode::Big] {
+            assert_eq!(mode.to_string().parse::<Mode>(), Ok(mode));
+        }
+        assert!("wrapping".parse::<Mode>().is_err());
+    }
+}
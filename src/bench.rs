@@ -0,0 +1,128 @@
+use std::time::{Duration, Instant};
+
+use crate::{Part, PuzzleError, Registration};
+
+// Stats summarises the wall-clock times of a number of runs of the same thing
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    // from_samples works out the stats for some timings, or None if there weren't any
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        // for an even number of samples take the mean of the middle two, which is what people generally expect
+        let middle = sorted.len() / 2;
+        let median = match sorted.len() % 2 {
+            0 => (sorted[middle - 1] + sorted[middle]) / 2,
+            _ => sorted[middle],
+        };
+
+        return Some(Stats {
+            runs: sorted.len(),
+            min: sorted[0],
+            median,
+            max: sorted[sorted.len() - 1],
+        });
+    }
+}
+
+// PartBench is the result of benchmarking one part of a day
+#[derive(Clone, Copy, Debug)]
+pub struct PartBench {
+    pub day: u32,
+    pub part: Part,
+    // the time to run the whole part, including parsing the input
+    pub total: Stats,
+    // these are only there for a Solution, which parses the input once for both parts:
+    // the time to only parse the input, and the time to solve the part from the input once it's parsed
+    pub parse: Option<Stats>,
+    pub solve: Option<Stats>,
+}
+
+// time runs f once, returning its result and how long it took (which parallel uses to time each part too)
+pub(crate) fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    return (result, start.elapsed());
+}
+
+// bench_part runs one part of a day `iterations` times (at least once), stopping at the first error
+pub fn bench_part(registration: &Registration, part: Part, input: &str, iterations: usize) -> Result<PartBench, PuzzleError> {
+    let iterations = iterations.max(1);
+
+    let mut totals = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let (result, elapsed) = time(|| registration.solve(part, input));
+        result?;
+        totals.push(elapsed);
+    }
+
+    let mut parses = Vec::with_capacity(iterations);
+    let mut solves = Vec::with_capacity(iterations);
+    // the part has already run fine above, so parsing the input can't fail here
+    let parsed = registration.parse(input).map_err(|e| e.in_part(registration.day, part))?;
+    if parsed.parsed_once() {
+        for _ in 0..iterations {
+            // (the parsed input is dropped after it's been timed, so that doesn't count towards the parse)
+            let (_, elapsed) = time(|| registration.parse(input));
+            parses.push(elapsed);
+        }
+        for _ in 0..iterations {
+            let (result, elapsed) = time(|| registration.solve_parsed(parsed.as_ref(), part));
+            result?;
+            solves.push(elapsed);
+        }
+    }
+
+    return Ok(PartBench {
+        day: registration.day,
+        part,
+        // we always have at least one run, so this is always Some
+        total: Stats::from_samples(&totals).unwrap(),
+        parse: Stats::from_samples(&parses),
+        solve: Stats::from_samples(&solves),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(ms: &[u64]) -> Vec<Duration> {
+        return ms.iter().map(|&ms| Duration::from_millis(ms)).collect();
+    }
+
+    #[test]
+    fn no_samples_have_no_stats() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn one_sample_is_every_stat() {
+        let one = Duration::from_millis(7);
+        assert_eq!(Stats::from_samples(&[one]), Some(Stats { runs: 1, min: one, median: one, max: one }));
+    }
+
+    #[test]
+    fn an_odd_number_of_samples_has_a_middle_one() {
+        let stats = Stats::from_samples(&millis(&[9, 1, 4, 30, 2])).unwrap();
+        assert_eq!(stats, Stats { runs: 5, min: Duration::from_millis(1), median: Duration::from_millis(4), max: Duration::from_millis(30) });
+    }
+
+    #[test]
+    fn an_even_number_of_samples_averages_the_middle_two() {
+        let stats = Stats::from_samples(&millis(&[8, 3, 100, 1])).unwrap();
+        assert_eq!(stats, Stats { runs: 4, min: Duration::from_millis(1), median: Duration::from_micros(5500), max: Duration::from_millis(100) });
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{Add, Mul};
+use std::str::FromStr;
+
+// BigUint is a non-negative integer of any size, for the few answers that don't fit in even a u128
+// it only does what the solvers need (adding, multiplying, and reading and writing decimal), so it's a lot smaller than a real bignum crate
+
+// the digits are base 2^32, least significant first, with no zeros at the most significant end (so zero has no digits at all)
+// that keeps each value's representation unique, so the derived PartialEq is the same as comparing the numbers
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        return BigUint { limbs: Vec::new() };
+    }
+
+    fn trimmed(mut limbs: Vec<u32>) -> BigUint {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        return BigUint { limbs };
+    }
+
+    pub fn is_zero(&self) -> bool {
+        return self.limbs.is_empty();
+    }
+
+    // to_u128 is the value as a u128, if it fits in one
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+        return Some(self.limbs.iter().rev().fold(0, |acc, &limb| (acc << 32) | limb as u128));
+    }
+
+    // mul_add_small sets self to self * m + a, which is all that reading decimal needs (whether from a string, or a digit at a time)
+    pub(crate) fn mul_add_small(&mut self, m: u32, a: u32) {
+        let mut carry = a as u64;
+        for limb in self.limbs.iter_mut() {
+            let v = *limb as u64 * m as u64 + carry;
+            *limb = v as u32;
+            carry = v >> 32;
+        }
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+        // (m could have been 0)
+        *self = BigUint::trimmed(std::mem::take(&mut self.limbs));
+    }
+
+    // div_rem_small divides self by d in place, returning the remainder, which is all that writing decimal needs
+    fn div_rem_small(&mut self, d: u32) -> u32 {
+        let mut remainder = 0_u64;
+        for limb in self.limbs.iter_mut().rev() {
+            let v = (remainder << 32) | *limb as u64;
+            *limb = (v / d as u64) as u32;
+            remainder = v % d as u64;
+        }
+        *self = BigUint::trimmed(std::mem::take(&mut self.limbs));
+        return remainder as u32;
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(mut v: u128) -> BigUint {
+        let mut limbs = Vec::new();
+        while v > 0 {
+            limbs.push(v as u32);
+            v >>= 32;
+        }
+        return BigUint { limbs };
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(v: u64) -> BigUint {
+        return BigUint::from(v as u128);
+    }
+}
+
+impl From<usize> for BigUint {
+    fn from(v: usize) -> BigUint {
+        return BigUint::from(v as u128);
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: BigUint) -> BigUint {
+        let (mut long, short) = match self.limbs.len() >= other.limbs.len() {
+            true => (self.limbs, other.limbs),
+            false => (other.limbs, self.limbs),
+        };
+        let mut carry = 0_u64;
+        for (i, limb) in long.iter_mut().enumerate() {
+            if i >= short.len() && carry == 0 {
+                break;
+            }
+            let v = *limb as u64 + short.get(i).copied().unwrap_or(0) as u64 + carry;
+            *limb = v as u32;
+            carry = v >> 32;
+        }
+        if carry > 0 {
+            long.push(carry as u32);
+        }
+        return BigUint { limbs: long };
+    }
+}
+
+impl Mul for BigUint {
+    type Output = BigUint;
+
+    // this is plain long multiplication, which is plenty fast for numbers of a few hundred digits
+    fn mul(self, other: BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+        let mut limbs = vec![0_u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0_u64;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                // this can't overflow: (2^32 - 1)^2 + 2 * (2^32 - 1) is exactly 2^64 - 1
+                let v = a as u64 * b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = v as u32;
+                carry = v >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        return BigUint::trimmed(limbs);
+    }
+}
+
+impl Sum for BigUint {
+    fn sum<I: Iterator<Item = BigUint>>(iter: I) -> BigUint {
+        return iter.fold(BigUint::zero(), |acc, v| acc + v);
+    }
+}
+
+impl Product for BigUint {
+    fn product<I: Iterator<Item = BigUint>>(iter: I) -> BigUint {
+        return iter.fold(BigUint::from(1_u64), |acc, v| acc * v);
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.pad("0");
+        }
+        // peel off nine decimal digits at a time, least significant first
+        let mut rest = self.clone();
+        let mut chunks = Vec::new();
+        while !rest.is_zero() {
+            chunks.push(rest.div_rem_small(1_000_000_000));
+        }
+        let mut digits = chunks.pop().unwrap().to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{chunk:09}"));
+        }
+        return f.pad(&digits);
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<BigUint, String> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("{s:?} is not a non-negative integer"));
+        }
+        let mut v = BigUint::zero();
+        for b in s.bytes() {
+            v.mul_add_small(10, (b - b'0') as u32);
+        }
+        return Ok(v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_u128_arithmetic() {
+        let values = [0_u128, 1, 9, u32::MAX as u128, u32::MAX as u128 + 1, u64::MAX as u128, 123456789012345678901234567];
+        for &a in &values {
+            assert_eq!(BigUint::from(a).to_u128(), Some(a));
+            assert_eq!(BigUint::from(a).to_string(), a.to_string());
+            assert_eq!(a.to_string().parse::<BigUint>(), Ok(BigUint::from(a)));
+            for &b in &values {
+                if let Some(sum) = a.checked_add(b) {
+                    assert_eq!(BigUint::from(a) + BigUint::from(b), BigUint::from(sum));
+                }
+                if let Some(product) = a.checked_mul(b) {
+                    assert_eq!(BigUint::from(a) * BigUint::from(b), BigUint::from(product));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn goes_past_u128() {
+        let big = BigUint::from(u128::MAX) * BigUint::from(u128::MAX);
+        assert_eq!(big.to_u128(), None);
+        assert_eq!(big.to_string(), "115792089237316195423570985008687907852589419931798687112530834793049593217025");
+        assert_eq!(big.to_string().parse::<BigUint>(), Ok(big));
+        assert_eq!((0..30).map(|_| BigUint::from(10_u64)).product::<BigUint>().to_string(), format!("1{}", "0".repeat(30)));
+    }
+
+    #[test]
+    fn rejects_anything_but_digits() {
+        assert!("".parse::<BigUint>().is_err());
+        assert!("-1".parse::<BigUint>().is_err());
+        assert!("12a".parse::<BigUint>().is_err());
+        assert_eq!("000".parse::<BigUint>(), Ok(BigUint::zero()));
+    }
+}
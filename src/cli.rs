@@ -0,0 +1,464 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use aoc_2025::Part;
+use aoc_2025::arithmetic::Mode;
+
+pub const USAGE: &str = "\
+aoc - Advent of Code 2025 solutions
+
+USAGE:
+    aoc run <day> [--part 1|2] [--input PATH|- | --input-dir DIR] [--format text|json|csv]
+    aoc all [--part 1|2] [--input-dir DIR] [--format text|json|csv] [--jobs N]
+    aoc list
+    aoc bench <day> [--part 1|2] [--input PATH|- | --input-dir DIR] [--iterations N]
+    aoc bench all [--part 1|2] [--input-dir DIR] [--iterations N]
+    aoc verify [--input-dir DIR] [--answers-dir DIR]
+    aoc import <day> <PATH|-> [--input-dir DIR] [--force]
+    aoc new <day> [--title TITLE]
+    aoc trace <day> [--input PATH|- | --input-dir DIR] [--format csv|json]
+    aoc help
+
+COMMANDS:
+    run      solve one day, reading its input from --input (or DIR/dayNN.txt without one)
+    all      solve every registered day, reading DIR/dayNN.txt for each
+    list     list every registered day
+    bench    time each part of a day (or every day) over a number of runs
+    verify   check every day's answers against those recorded in DIR/dayN.toml
+    import   copy a downloaded input (or stdin, for -) to DIR/dayNN.txt, with \n line endings
+    new      start a new day: src/aoc/dayN.rs, its entry in src/aoc.rs, and an empty examples/dayN.txt
+    trace    write out each step a day takes through its input, for days that can (currently day 1: each
+             rotation of the dial, where it started and ended, and how many times it pointed at 0)
+    help     show this message
+
+OPTIONS:
+    -p, --part 1|2        only run one part of each day (both parts run by default)
+    -i, --input PATH|-    read the puzzle input from PATH, or from stdin if PATH is -
+    -d, --input-dir DIR   directory holding the dayNN.txt inputs (default: $AOC_INPUT_DIR if it's set,
+                          then input_dir from ./aoc.toml if there is one, then inputs)
+    -f, --format FORMAT   how run and all print results: text (default), json (an array with one
+                          record per day and part) or csv (a header, then one row per day and part);
+                          trace writes csv (default) or json in the same way, with one record per step
+    -n, --iterations N    how many times bench runs each part (default: 10)
+    -j, --jobs N          how many days (and parts) all solves at once, on separate threads (default: 1)
+    -a, --answers-dir DIR directory holding the recorded dayN.toml answers (default: answers)
+        --force           let import overwrite an input that's already there
+    -t, --title TITLE     the puzzle's title, for new (default: Day N)
+        --arithmetic MODE what the solvers do if a number gets too big for its type: unchecked (default;
+                          wraps around in a release build), checked (reports an error saying what
+                          overflowed) or big (as checked, but days that can use arbitrary precision do)
+    -v, --verbose         log to stderr: -v for info, -vv for debug, -vvv for trace
+                          (without -v, the level comes from AOC_LOG=off|info|debug|trace)
+    -h, --help            show this message
+
+EXIT CODES:
+    0    every part ran successfully
+    1    a puzzle failed (or gave the wrong answer for verify), or its input couldn't be read
+    2    the command line was invalid
+";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl std::str::FromStr for Format {
+    type Err = UsageError;
+
+    fn from_str(s: &str) -> Result<Format, UsageError> {
+        return match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => usage_error(format!("--format must be text, json or csv, not {s:?}")),
+        };
+    }
+}
+
+// BenchTarget is what to benchmark: one day (with its input, if one was given), or every day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BenchTarget {
+    Day(u32, Option<InputSource>),
+    All,
+}
+
+// an input_dir of None means the flag wasn't given, and it's up to aoc_2025::inputs::input_dir to find the directory
+// likewise a day with no input is read from that directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Run { day: u32, part: Option<Part>, input: Option<InputSource>, input_dir: Option<PathBuf>, format: Format },
+    All { part: Option<Part>, input_dir: Option<PathBuf>, format: Format, jobs: usize },
+    List,
+    Bench { target: BenchTarget, part: Option<Part>, input_dir: Option<PathBuf>, iterations: usize },
+    Verify { input_dir: Option<PathBuf>, answers_dir: PathBuf },
+    Import { day: u32, from: InputSource, input_dir: Option<PathBuf>, force: bool },
+    New { day: u32, title: String },
+    Trace { day: u32, input: Option<InputSource>, input_dir: Option<PathBuf>, format: Format },
+    Help,
+}
+
+// Args is everything from the command line: what to do, how much to log while doing it, and how to handle overflow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    pub command: Command,
+    // the number of -v flags given
+    pub verbosity: usize,
+    pub arithmetic: Mode,
+}
+
+// UsageError is anything wrong with the command line itself, as opposed to with running a puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageError(pub String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.0);
+    }
+}
+
+// Flags holds every flag we've seen; each command then checks it only got the ones that make sense for it
+#[derive(Default)]
+struct Flags {
+    part: Option<Part>,
+    input: Option<InputSource>,
+    input_dir: Option<PathBuf>,
+    iterations: Option<usize>,
+    jobs: Option<usize>,
+    answers_dir: Option<PathBuf>,
+    format: Option<Format>,
+    title: Option<String>,
+    force: bool,
+    verbosity: usize,
+    arithmetic: Option<Mode>,
+    help: bool,
+}
+
+fn usage_error<T>(message: impl Into<String>) -> Result<T, UsageError> {
+    return Err(UsageError(message.into()));
+}
+
+// parse_args turns the arguments (not including the program name) into a Command
+pub fn parse_args<I>(args: I) -> Result<Args, UsageError>
+where I: IntoIterator<Item = String> {
+    let mut args = args.into_iter();
+    let mut positional = Vec::new();
+    let mut flags = Flags::default();
+
+    while let Some(arg) = args.next() {
+        // flags can be given as either `--flag value` or `--flag=value`
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if arg.starts_with("--") => (name.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| match inline_value.clone().or_else(|| args.next()) {
+            Some(v) => Ok(v),
+            None => usage_error(format!("{name} needs a value")),
+        };
+
+        // switches don't take a value, so e.g. --force=no is an error rather than quietly meaning --force
+        let is_switch = matches!(name.as_str(), "-h" | "--help" | "--force" | "--verbose");
+        if is_switch && inline_value.is_some() {
+            return usage_error(format!("{name} doesn't take a value"));
+        }
+
+        match name.as_str() {
+            "-h" | "--help" => flags.help = true,
+            "--force" => flags.force = true,
+            "--verbose" => flags.verbosity += 1,
+            // -v can be repeated either as -v -v or -vv
+            _ if name.len() > 1 && name.starts_with('-') && name[1..].chars().all(|c| c == 'v') => flags.verbosity += name.len() - 1,
+            "-p" | "--part" => {
+                flags.part = Some(value(&name)?.parse::<Part>().map_err(UsageError)?);
+            }
+            "-i" | "--input" => flags.input = Some(input_source(value(&name)?)),
+            "-d" | "--input-dir" => flags.input_dir = Some(PathBuf::from(value(&name)?)),
+            "-f" | "--format" => flags.format = Some(value(&name)?.parse::<Format>()?),
+            "-a" | "--answers-dir" => flags.answers_dir = Some(PathBuf::from(value(&name)?)),
+            "-t" | "--title" => flags.title = Some(value(&name)?),
+            "-n" | "--iterations" => flags.iterations = Some(positive_number(&name, value(&name)?)?),
+            "-j" | "--jobs" => flags.jobs = Some(positive_number(&name, value(&name)?)?),
+            "--arithmetic" => flags.arithmetic = Some(value(&name)?.parse::<Mode>().map_err(UsageError)?),
+            // a lone "-" is a value, not a flag
+            _ if name.starts_with('-') && name != "-" => return usage_error(format!("unknown option {name}")),
+            _ => positional.push(arg),
+        }
+    }
+
+    if flags.help {
+        return Ok(Args { command: Command::Help, verbosity: flags.verbosity, arithmetic: Mode::Unchecked });
+    }
+
+    let (command, rest) = match positional.split_first() {
+        Some((command, rest)) => (command.as_str(), rest),
+        None => return usage_error("no command given"),
+    };
+
+    let command = match command {
+        "run" => {
+            let (input, input_dir) = day_input(command, &mut flags)?;
+            Command::Run {
+                day: day_argument(command, rest)?,
+                part: flags.part.take(),
+                input,
+                input_dir,
+                format: flags.format.take().unwrap_or(Format::Text),
+            }
+        }
+        "all" => {
+            no_arguments(command, rest)?;
+            Command::All {
+                part: flags.part.take(),
+                input_dir: flags.input_dir.take(),
+                format: flags.format.take().unwrap_or(Format::Text),
+                jobs: flags.jobs.take().unwrap_or(1),
+            }
+        }
+        "list" => {
+            no_arguments(command, rest)?;
+            Command::List
+        }
+        "bench" => {
+            let (target, input_dir) = match rest {
+                [all] if all == "all" => (BenchTarget::All, flags.input_dir.take()),
+                _ => {
+                    let (input, input_dir) = day_input(command, &mut flags)?;
+                    (BenchTarget::Day(day_argument(command, rest)?, input), input_dir)
+                }
+            };
+            Command::Bench {
+                target,
+                part: flags.part.take(),
+                input_dir,
+                iterations: flags.iterations.take().unwrap_or(10),
+            }
+        }
+        "verify" => {
+            no_arguments(command, rest)?;
+            Command::Verify {
+                input_dir: flags.input_dir.take(),
+                answers_dir: flags.answers_dir.take().unwrap_or_else(|| PathBuf::from("answers")),
+            }
+        }
+        "import" => {
+            let from = match rest {
+                [_, from] => input_source(from.clone()),
+                _ => return usage_error("import needs a day number and the path of the input to import (or - for stdin)"),
+            };
+            Command::Import {
+                day: day_argument(command, &rest[..1])?,
+                from,
+                input_dir: flags.input_dir.take(),
+                force: std::mem::take(&mut flags.force),
+            }
+        }
+        "new" => {
+            let day = day_argument(command, rest)?;
+            Command::New { day, title: flags.title.take().unwrap_or_else(|| format!("Day {day}")) }
+        }
+        "trace" => {
+            let (input, input_dir) = day_input(command, &mut flags)?;
+            let format = match flags.format.take() {
+                Some(Format::Text) => return usage_error("trace only writes csv or json"),
+                format => format.unwrap_or(Format::Csv),
+            };
+            Command::Trace { day: day_argument(command, rest)?, input, input_dir, format }
+        }
+        "help" => {
+            no_arguments(command, rest)?;
+            Command::Help
+        }
+        _ => return usage_error(format!("unknown command {command:?}")),
+    };
+
+    // anything still left over wasn't meaningful for this command
+    if flags.part.is_some() {
+        return usage_error("--part only applies to run, all and bench");
+    }
+    if flags.input.is_some() {
+        return usage_error("--input only applies to run, bench and trace");
+    }
+    if flags.input_dir.is_some() {
+        return usage_error("--input-dir only applies to run, all, bench, verify, import and trace");
+    }
+    if flags.iterations.is_some() {
+        return usage_error("--iterations only applies to bench");
+    }
+    if flags.jobs.is_some() {
+        return usage_error("--jobs only applies to all");
+    }
+    if flags.format.is_some() {
+        return usage_error("--format only applies to run, all and trace");
+    }
+    if flags.answers_dir.is_some() {
+        return usage_error("--answers-dir only applies to verify");
+    }
+    if flags.title.is_some() {
+        return usage_error("--title only applies to new");
+    }
+    if flags.force {
+        return usage_error("--force only applies to import");
+    }
+
+    // like -v, --arithmetic applies to whichever command it's given with
+    return Ok(Args { command, verbosity: flags.verbosity, arithmetic: flags.arithmetic.unwrap_or(Mode::Unchecked) });
+}
+
+fn positive_number(name: &str, v: String) -> Result<usize, UsageError> {
+    return match v.parse::<usize>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => usage_error(format!("{name} must be a positive number, not {v:?}")),
+    };
+}
+
+fn input_source(arg: String) -> InputSource {
+    return match arg.as_str() {
+        "-" => InputSource::Stdin,
+        _ => InputSource::File(PathBuf::from(arg)),
+    };
+}
+
+// day_input is where a single day's input comes from: either --input, or the day's file in --input-dir (or the default directory)
+fn day_input(command: &str, flags: &mut Flags) -> Result<(Option<InputSource>, Option<PathBuf>), UsageError> {
+    if flags.input.is_some() && flags.input_dir.is_some() {
+        return usage_error(format!("{command} takes either --input or --input-dir, not both"));
+    }
+    return Ok((flags.input.take(), flags.input_dir.take()));
+}
+
+fn day_argument(command: &str, rest: &[String]) -> Result<u32, UsageError> {
+    return match rest {
+        [day] => day.parse::<u32>().or_else(|_| usage_error(format!("day number {day:?} is not a number"))),
+        [] => usage_error(format!("{command} needs a day number")),
+        _ => usage_error(format!("{command} takes a single day number, got {}", rest.join(" "))),
+    };
+}
+
+fn no_arguments(command: &str, rest: &[String]) -> Result<(), UsageError> {
+    if !rest.is_empty() {
+        return usage_error(format!("{command} doesn't take any arguments, got {}", rest.join(" ")));
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Args, UsageError> {
+        return parse_args(args.split_whitespace().map(|arg| arg.to_string()));
+    }
+
+    fn command(args: &str) -> Command {
+        return parse(args).unwrap_or_else(|e| panic!("{args:?}: {e}")).command;
+    }
+
+    // error is the message for a command line that's meant to be rejected
+    fn error(args: &str) -> String {
+        return match parse(args) {
+            Ok(parsed) => panic!("{args:?} should have been rejected, but parsed as {parsed:?}"),
+            Err(UsageError(message)) => message,
+        };
+    }
+
+    #[test]
+    fn parses_every_command() {
+        assert_eq!(command("run 3"), Command::Run { day: 3, part: None, input: None, input_dir: None, format: Format::Text });
+        assert_eq!(command("all"), Command::All { part: None, input_dir: None, format: Format::Text, jobs: 1 });
+        assert_eq!(command("list"), Command::List);
+        assert_eq!(command("bench 4"), Command::Bench { target: BenchTarget::Day(4, None), part: None, input_dir: None, iterations: 10 });
+        assert_eq!(command("bench all -n 3"), Command::Bench { target: BenchTarget::All, part: None, input_dir: None, iterations: 3 });
+        assert_eq!(command("verify"), Command::Verify { input_dir: None, answers_dir: PathBuf::from("answers") });
+        assert_eq!(
+            command("import 5 dl.txt --force"),
+            Command::Import { day: 5, from: InputSource::File(PathBuf::from("dl.txt")), input_dir: None, force: true },
+        );
+        assert_eq!(command("new 10"), Command::New { day: 10, title: "Day 10".to_string() });
+        assert_eq!(command("trace 1"), Command::Trace { day: 1, input: None, input_dir: None, format: Format::Csv });
+        assert_eq!(command("help"), Command::Help);
+        assert_eq!(command("run 3 --help"), Command::Help);
+    }
+
+    #[test]
+    fn parses_flags_either_way() {
+        let run = Command::Run { day: 2, part: Some(Part::Two), input: Some(InputSource::Stdin), input_dir: None, format: Format::Json };
+        assert_eq!(command("run 2 --part 2 --input - --format json"), run);
+        assert_eq!(command("run 2 --part=2 --input=- --format=json"), run);
+        assert_eq!(command("run 2 -p 2 -i - -f json"), run);
+        assert_eq!(command("run 2 -d in"), Command::Run { day: 2, part: None, input: None, input_dir: Some(PathBuf::from("in")), format: Format::Text });
+        assert_eq!(command("all -j 4 --format csv"), Command::All { part: None, input_dir: None, format: Format::Csv, jobs: 4 });
+        assert_eq!(command("verify --answers-dir a -d i"), Command::Verify { input_dir: Some(PathBuf::from("i")), answers_dir: PathBuf::from("a") });
+        assert_eq!(command("new 10 --title=Lobby"), Command::New { day: 10, title: "Lobby".to_string() });
+        assert_eq!(command("trace 1 --format json"), Command::Trace { day: 1, input: None, input_dir: None, format: Format::Json });
+    }
+
+    #[test]
+    fn counts_verbosity_and_reads_the_arithmetic_mode() {
+        let args = parse("-v run 1 -vv --verbose --arithmetic big").unwrap();
+        assert_eq!((args.verbosity, args.arithmetic), (4, Mode::Big));
+        assert_eq!(parse("list").unwrap().arithmetic, Mode::Unchecked);
+    }
+
+    #[test]
+    fn rejects_bad_values() {
+        assert!(error("run 1 --part 3").contains("part must be 1 or 2"));
+        assert!(error("all --jobs 0").contains("--jobs must be a positive number"));
+        assert!(error("all --jobs many").contains("--jobs must be a positive number"));
+        assert!(error("bench 1 --iterations -1").contains("--iterations must be a positive number"));
+        assert!(error("run 1 --format xml").contains("--format must be text, json or csv"));
+        assert!(error("trace 1 --format text").contains("trace only writes csv or json"));
+        assert!(error("run 1 --arithmetic wide").contains("arithmetic must be one of"));
+        assert!(error("run 1 --input").contains("--input needs a value"));
+        assert!(error("run one").contains("is not a number"));
+    }
+
+    #[test]
+    fn switches_dont_take_values() {
+        assert_eq!(error("import 5 dl.txt --force=x"), "--force doesn't take a value");
+        assert_eq!(error("run 1 --verbose=3"), "--verbose doesn't take a value");
+        assert_eq!(error("--help=yes"), "--help doesn't take a value");
+    }
+
+    #[test]
+    fn rejects_the_wrong_arguments() {
+        assert_eq!(error(""), "no command given");
+        assert!(error("fly").contains("unknown command"));
+        assert!(error("run 1 --fast").contains("unknown option --fast"));
+        assert_eq!(error("run"), "run needs a day number");
+        assert!(error("run 1 2").contains("takes a single day number"));
+        assert!(error("list 2").contains("list doesn't take any arguments"));
+        assert!(error("help me").contains("help doesn't take any arguments"));
+        assert!(error("all 3").contains("all doesn't take any arguments"));
+        assert!(error("import 5").contains("import needs a day number and the path"));
+        assert!(error("run 1 --input x --input-dir y").contains("either --input or --input-dir"));
+    }
+
+    #[test]
+    fn rejects_flags_for_other_commands() {
+        assert!(error("list --part 1").contains("--part only applies"));
+        assert!(error("all --input x").contains("--input only applies"));
+        assert!(error("new 3 --input-dir x").contains("--input-dir only applies"));
+        assert!(error("run 1 -n 3").contains("--iterations only applies"));
+        assert!(error("run 1 --jobs 2").contains("--jobs only applies"));
+        assert!(error("bench 1 --format json").contains("--format only applies"));
+        assert!(error("run 1 --answers-dir a").contains("--answers-dir only applies"));
+        assert!(error("run 1 --title x").contains("--title only applies"));
+        assert!(error("run 1 --force").contains("--force only applies"));
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt;
+
+use crate::Part;
+
+// PuzzleError is what a Puzzle returns when it can't make sense of its input, rather than panicking and taking the whole binary down
+// solvers only know where in the input things went wrong, so the day and part are filled in afterwards by whatever ran them (see in_part)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PuzzleError {
+    pub day: Option<u32>,
+    pub part: Option<Part>,
+    // both of these are 1-indexed, like an editor would show them
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl PuzzleError {
+    // new is for errors that aren't about any particular place in the input (e.g. the input being empty)
+    pub fn new(message: impl Into<String>) -> PuzzleError {
+        return PuzzleError {
+            day: None,
+            part: None,
+            line: None,
+            column: None,
+            message: message.into(),
+        };
+    }
+
+    pub fn at(line: usize, column: usize, message: impl Into<String>) -> PuzzleError {
+        return PuzzleError {
+            line: Some(line),
+            column: Some(column),
+            ..PuzzleError::new(message)
+        };
+    }
+
+    // in_day is for errors that belong to a day but not to either part, e.g. from parsing the input once for both parts
+    pub fn in_day(self, day: u32) -> PuzzleError {
+        return PuzzleError {
+            day: Some(day),
+            ..self
+        };
+    }
+
+    pub fn in_part(self, day: u32, part: Part) -> PuzzleError {
+        return PuzzleError {
+            day: Some(day),
+            part: Some(part),
+            ..self
+        };
+    }
+}
+
+// column_of works out the (1-indexed) column at which `token` starts within `line`
+// token must be a slice of line (e.g. something that came out of split or trim), otherwise this is meaningless
+pub fn column_of(line: &str, token: &str) -> usize {
+    return token.as_ptr() as usize - line.as_ptr() as usize + 1;
+}
+
+// position_of works out the (1-indexed) line and column at which `token` starts within the whole input, for input that isn't read a line at a time
+// like column_of, token must be a slice of input
+pub fn position_of(input: &str, token: &str) -> (usize, usize) {
+    let before = &input[..token.as_ptr() as usize - input.as_ptr() as usize];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    return (before.matches('\n').count() + 1, column_of(&input[line_start..], token));
+}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // e.g. "day 9 part 1 (line 3, column 5): invalid coordinate "x""
+        let mut context = Vec::new();
+        if let Some(day) = self.day {
+            context.push(format!("day {day}"));
+        }
+        if let Some(part) = self.part {
+            context.push(format!("part {part}"));
+        }
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => context.push(format!("(line {line}, column {column})")),
+            (Some(line), None) => context.push(format!("(line {line})")),
+            _ => {},
+        }
+
+        if context.is_empty() {
+            return write!(f, "{}", self.message);
+        }
+        return write!(f, "{}: {}", context.join(" "), self.message);
+    }
+}
+
+impl std::error::Error for PuzzleError {}
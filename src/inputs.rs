@@ -0,0 +1,120 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::minitoml;
+
+// puzzle inputs live in a cache directory as dayNN.txt (e.g. inputs/day08.txt), so that `aoc run 8` can find its input without being told
+// the directory is, in order of preference:
+// - whatever was passed on the command line (--input-dir)
+// - the AOC_INPUT_DIR environment variable
+// - input_dir in an aoc.toml config file in the current directory
+// - inputs/
+
+pub const DIR_ENV_VAR: &str = "AOC_INPUT_DIR";
+pub const CONFIG_FILE: &str = "aoc.toml";
+pub const DEFAULT_DIR: &str = "inputs";
+
+// input_dir works out which directory inputs should be read from (or imported to), given the --input-dir flag if there was one
+pub fn input_dir(flag: Option<&Path>) -> io::Result<PathBuf> {
+    if let Some(dir) = flag {
+        return Ok(dir.to_path_buf());
+    }
+    if let Some(dir) = std::env::var_os(DIR_ENV_VAR) {
+        return Ok(PathBuf::from(dir));
+    }
+    if let Some(dir) = input_dir_from_config(Path::new(CONFIG_FILE))? {
+        return Ok(dir);
+    }
+    return Ok(PathBuf::from(DEFAULT_DIR));
+}
+
+// input_dir_from_config reads input_dir from a config file, if the file exists and sets it
+fn input_dir_from_config(path: &Path) -> io::Result<Option<PathBuf>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {message}", path.display()));
+    let mut dir = None;
+    for (line_number, key, value) in minitoml::parse(&contents).map_err(|e| invalid(e.to_string()))? {
+        match key.as_str() {
+            "input_dir" => dir = Some(PathBuf::from(value.into_string())),
+            other => return Err(invalid(format!("line {line_number}: unknown key {other:?}"))),
+        }
+    }
+    return Ok(dir);
+}
+
+// input_path is where a day's input lives in the given directory
+pub fn input_path(dir: &Path, day: u32) -> PathBuf {
+    return dir.join(format!("day{day:02}.txt"));
+}
+
+// normalise_line_endings turns Windows (\r\n) and old Mac (\r) line endings into plain \n, which is what all the solvers expect
+pub fn normalise_line_endings(input: &str) -> String {
+    return input.replace("\r\n", "\n").replace('\r', "\n");
+}
+
+// import copies a downloaded input into the directory under the right name, normalising its line endings on the way
+// it won't overwrite an existing input unless asked to, and returns where the input ended up
+pub fn import(dir: &Path, day: u32, input: &str, overwrite: bool) -> io::Result<PathBuf> {
+    let path = input_path(dir, day);
+    if path.exists() && !overwrite {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists", path.display())));
+    }
+
+    fs::create_dir_all(dir)?;
+    fs::write(&path, normalise_line_endings(input))?;
+    return Ok(path);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_path_pads_the_day() {
+        assert_eq!(input_path(Path::new("inputs"), 8), Path::new("inputs/day08.txt"));
+        assert_eq!(input_path(Path::new("inputs"), 12), Path::new("inputs/day12.txt"));
+    }
+
+    #[test]
+    fn normalises_every_kind_of_line_ending() {
+        assert_eq!(normalise_line_endings("a\r\nb\rc\nd\r\n"), "a\nb\nc\nd\n");
+        assert_eq!(normalise_line_endings("no newline"), "no newline");
+    }
+
+    #[test]
+    fn reads_input_dir_from_config() {
+        let dir = std::env::temp_dir().join(format!("aoc-2025-config-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config = dir.join(CONFIG_FILE);
+
+        fs::write(&config, "# where my inputs are\ninput_dir = \"/data/aoc\"\n").unwrap();
+        assert_eq!(input_dir_from_config(&config).unwrap(), Some(PathBuf::from("/data/aoc")));
+
+        fs::write(&config, "input_dri = \"typo\"\n").unwrap();
+        assert!(input_dir_from_config(&config).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(input_dir_from_config(&config).unwrap(), None);
+    }
+
+    #[test]
+    fn import_refuses_to_overwrite() {
+        let dir = std::env::temp_dir().join(format!("aoc-2025-import-test-{}", std::process::id()));
+
+        let path = import(&dir, 3, "12\r\n34\r\n", false).unwrap();
+        assert_eq!(path, dir.join("day03.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "12\n34\n");
+
+        assert_eq!(import(&dir, 3, "56\n", false).unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+        import(&dir, 3, "56\n", true).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "56\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
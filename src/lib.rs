@@ -0,0 +1,126 @@
+// the solvers live in this library so that they can be run in-process by other crates (and by tests), not just by the aoc binary
+pub mod answer;
+pub mod answers;
+pub mod aoc;
+pub mod arithmetic;
+pub mod bench;
+pub mod bigint;
+pub mod error;
+pub mod inputs;
+pub mod log;
+mod minitoml;
+pub mod parallel;
+pub mod report;
+
+use std::fmt;
+use std::str::FromStr;
+
+pub use aoc::{lookup, puzzle, registry, Registration};
+pub use answer::Answer;
+pub use error::PuzzleError;
+
+// the input is borrowed as a &str, so that solvers can parse it in place (e.g. with lines()) without copying any of it
+pub trait Puzzle {
+    fn part1(&self, input: &str) -> Result<Answer, PuzzleError>;
+    fn part2(&self, input: &str) -> Result<Answer, PuzzleError>;
+
+    // parse parses the input once, ready for either part to be solved from it as many times as needed
+    // only a Solution can do that, so every other puzzle leaves this as None, and each of its parts parses the input itself
+    fn parse<'a>(&'a self, _input: &'a str) -> Option<Result<Box<dyn ParsedInput + 'a>, PuzzleError>> {
+        return None;
+    }
+}
+
+// Solution is for puzzles whose parts both start by parsing the input in the same way
+// the runner can then parse the input once for both parts (and time the parse on its own), rather than each part parsing it again
+// every Solution is also a Puzzle (see below), so it's registered in just the same way
+pub trait Solution {
+    // Parsed can borrow from the input, so that parsing it doesn't have to copy it
+    type Parsed<'a>: Send + Sync + 'a;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, PuzzleError>;
+    fn part1(&self, parsed: &Self::Parsed<'_>) -> Result<Answer, PuzzleError>;
+    fn part2(&self, parsed: &Self::Parsed<'_>) -> Result<Answer, PuzzleError>;
+}
+
+// ParsedInput is a puzzle's input once it's ready for either part to be solved from it
+// for a Solution that's its Parsed, and for any other Puzzle it's just the input (see Registration::parse)
+pub trait ParsedInput: Send + Sync {
+    fn solve(&self, part: Part) -> Result<Answer, PuzzleError>;
+
+    // parsed_once is whether the input really was parsed up front, i.e. whether parsing it is worth timing
+    fn parsed_once(&self) -> bool {
+        return true;
+    }
+}
+
+// SolutionInput is a Solution's input once parsed, along with the Solution to solve it with
+struct SolutionInput<'a, S: Solution> {
+    solution: &'a S,
+    parsed: S::Parsed<'a>,
+}
+
+impl<S: Solution + Sync> ParsedInput for SolutionInput<'_, S> {
+    fn solve(&self, part: Part) -> Result<Answer, PuzzleError> {
+        return match part {
+            Part::One => self.solution.part1(&self.parsed),
+            Part::Two => self.solution.part2(&self.parsed),
+        };
+    }
+}
+
+// this is the adapter that lets a Solution be used anywhere a Puzzle can, by parsing the input then solving from that
+impl<S: Solution + Sync> Puzzle for S {
+    fn part1(&self, input: &str) -> Result<Answer, PuzzleError> {
+        return Solution::part1(self, &Solution::parse(self, input)?);
+    }
+    fn part2(&self, input: &str) -> Result<Answer, PuzzleError> {
+        return Solution::part2(self, &Solution::parse(self, input)?);
+    }
+    fn parse<'a>(&'a self, input: &'a str) -> Option<Result<Box<dyn ParsedInput + 'a>, PuzzleError>> {
+        return Some(Solution::parse(self, input).map(|parsed| Box::new(SolutionInput { solution: self, parsed }) as Box<dyn ParsedInput + 'a>));
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        return match self {
+            Part::One => 1,
+            Part::Two => 2,
+        };
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.number());
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Part, String> {
+        return match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("part must be 1 or 2, not {s:?}")),
+        };
+    }
+}
+
+// solve runs just the one part of a puzzle, so that e.g. a slow part 2 doesn't have to run to check a part 1 fix
+pub fn solve<P: Puzzle + ?Sized>(puzzle: &P, part: Part, input: &str) -> Result<Answer, PuzzleError> {
+    return match part {
+        Part::One => puzzle.part1(input),
+        Part::Two => puzzle.part2(input),
+    };
+}
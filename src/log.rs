@@ -0,0 +1,144 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// this is the tiniest possible logging layer: a global level, and info!/debug!/trace! macros that write to stderr when it's high enough
+// it means the solvers can keep all of their debug chatter without it getting mixed into the answers on stdout (or slowing big inputs down)
+
+// ENV_VAR is the environment variable that sets the level, e.g. AOC_LOG=debug
+pub const ENV_VAR: &str = "AOC_LOG";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Off = 0,
+    // what's being run, and how long it took
+    Info = 1,
+    // a few lines of summary per part, e.g. how much of the input was parsed
+    Debug = 2,
+    // everything, e.g. the state after every line of input (this can be a lot of output)
+    Trace = 3,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+
+impl Level {
+    // from_verbosity maps the number of -v flags to a level, so -v is info, -vv is debug and -vvv (or more) is trace
+    pub fn from_verbosity(count: usize) -> Level {
+        return match count {
+            0 => Level::Off,
+            1 => Level::Info,
+            2 => Level::Debug,
+            _ => Level::Trace,
+        };
+    }
+
+    fn from_u8(v: u8) -> Level {
+        return match v {
+            0 => Level::Off,
+            1 => Level::Info,
+            2 => Level::Debug,
+            _ => Level::Trace,
+        };
+    }
+
+    fn name(self) -> &'static str {
+        return match self {
+            Level::Off => "off",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f.pad(self.name());
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Level, String> {
+        return match s.trim().to_ascii_lowercase().as_str() {
+            "off" | "" => Ok(Level::Off),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!("log level must be one of off, info, debug or trace, not {s:?}")),
+        };
+    }
+}
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    return Level::from_u8(LEVEL.load(Ordering::Relaxed));
+}
+
+pub fn enabled(level: Level) -> bool {
+    return level != Level::Off && level <= self::level();
+}
+
+// level_from_env reads the level from ENV_VAR, if it's set
+pub fn level_from_env() -> Result<Option<Level>, String> {
+    return match std::env::var(ENV_VAR) {
+        Ok(v) => v.parse::<Level>().map(Some).map_err(|e| format!("{ENV_VAR}: {e}")),
+        Err(_) => Ok(None),
+    };
+}
+
+// write is what the macros call once they've checked the level, so it shouldn't need calling directly
+#[doc(hidden)]
+pub fn write(level: Level, module: &str, args: fmt::Arguments) {
+    eprintln!("[{level:<5} {module}] {args}");
+}
+
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::log::enabled($level) {
+            $crate::log::write($level, module_path!(), format_args!($($arg)*));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => { $crate::log!($crate::log::Level::Info, $($arg)*) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => { $crate::log!($crate::log::Level::Debug, $($arg)*) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => { $crate::log!($crate::log::Level::Trace, $($arg)*) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_are_ordered_by_verbosity() {
+        assert!(Level::Off < Level::Info);
+        assert!(Level::Info < Level::Debug);
+        assert!(Level::Debug < Level::Trace);
+        assert_eq!(Level::from_verbosity(0), Level::Off);
+        assert_eq!(Level::from_verbosity(2), Level::Debug);
+        assert_eq!(Level::from_verbosity(7), Level::Trace);
+    }
+
+    #[test]
+    fn parses_level_names() {
+        assert_eq!("DEBUG".parse::<Level>(), Ok(Level::Debug));
+        assert_eq!("trace".parse::<Level>(), Ok(Level::Trace));
+        assert!("loud".parse::<Level>().is_err());
+    }
+}
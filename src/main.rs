@@ -1,48 +1,514 @@
-mod aoc;
+mod cli;
+mod scaffold;
 
 use std::env;
-use std::fs::File;
-use std::io::Read;
-trait Puzzle {
-    fn part1(&self, input: &String) -> String;
-    fn part2(&self, input: &String) -> String;
-}
-
-fn main() -> std::io::Result<()> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 3 {
-        panic!("aoc must be invoked as `aoc <day_number> <input_filename>`")
-    }
-
-    let day = &args[1];
-
-    // make sure we have code for this day
-
-    let day_impl: Box<dyn Puzzle> = match day.as_str() {
-        "0" => Box::new(aoc::Day0),
-        "1" => Box::new(aoc::Day1),
-        "2" => Box::new(aoc::Day2),
-        "3" => Box::new(aoc::Day3),
-        "4" => Box::new(aoc::Day4),
-        "5" => Box::new(aoc::Day5),
-        "6" => Box::new(aoc::Day6),
-        "7" => Box::new(aoc::Day7),
-        "8" => Box::new(aoc::Day8),
-        "9" => Box::new(aoc::Day9),
-        default => panic!("day number {} is not yet implemented", default),
-    };
-    
-    // then get the input from the specified file
-    let filename = &args[2];
-
-    // NB: I know we should be unpacking the Result types here
-    let mut file = File::open(filename)?;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use aoc_2025::answers::{self, Answers, Outcome};
+use aoc_2025::aoc::Day1;
+use aoc_2025::arithmetic;
+use aoc_2025::bench::{self, PartBench};
+use aoc_2025::inputs;
+use aoc_2025::log::{self, Level};
+use aoc_2025::parallel::{self, DaySolved};
+use aoc_2025::report::{self, Record};
+use aoc_2025::{Answer, Part, Registration};
+use cli::{BenchTarget, Command, Format, InputSource};
+
+// see EXIT CODES in cli::USAGE
+const EXIT_FAILURE: u8 = 1;
+const EXIT_USAGE: u8 = 2;
+
+fn main() -> ExitCode {
+    let args = match cli::parse_args(env::args().skip(1)) {
+        Ok(a) => a,
+        Err(e) => return usage_failure(e),
+    };
+
+    // -v on the command line wins over the environment
+    let level = match args.verbosity {
+        0 => match log::level_from_env() {
+            Ok(level) => level.unwrap_or(Level::Off),
+            Err(e) => return usage_failure(cli::UsageError(e)),
+        },
+        v => Level::from_verbosity(v),
+    };
+    log::set_level(level);
+    arithmetic::set_mode(args.arithmetic);
+
+    return match args.command {
+        Command::Run { day, part, input, input_dir, format } => run(day, part, input, input_dir.as_deref(), format),
+        Command::All { part, input_dir, format, jobs } => all(part, input_dir.as_deref(), format, jobs),
+        Command::List => list(),
+        Command::Bench { target, part, input_dir, iterations } => bench(&target, part, input_dir.as_deref(), iterations),
+        Command::Verify { input_dir, answers_dir } => verify(input_dir.as_deref(), &answers_dir),
+        Command::Import { day, from, input_dir, force } => import(day, &from, input_dir.as_deref(), force),
+        Command::New { day, title } => new(day, &title),
+        Command::Trace { day, input, input_dir, format } => trace(day, input, input_dir.as_deref(), format),
+        Command::Help => {
+            print!("{}", cli::USAGE);
+            ExitCode::SUCCESS
+        }
+    };
+}
+
+fn usage_failure(e: cli::UsageError) -> ExitCode {
+    eprintln!("error: {e}");
+    eprintln!("run `aoc help` for usage");
+    return ExitCode::from(EXIT_USAGE);
+}
+
+fn lookup(day: u32) -> Result<&'static Registration, cli::UsageError> {
+    return aoc_2025::lookup(day).ok_or_else(|| cli::UsageError(format!("day number {day} is not yet implemented")));
+}
+
+// input_dir finds the input directory (see aoc_2025::inputs), reporting why if it can't, e.g. because aoc.toml is broken
+fn input_dir(flag: Option<&Path>) -> Result<PathBuf, ExitCode> {
+    return match inputs::input_dir(flag) {
+        Ok(dir) => {
+            aoc_2025::info!("using inputs in {}", dir.display());
+            Ok(dir)
+        }
+        Err(e) => {
+            eprintln!("error: couldn't work out the input directory: {e}");
+            Err(ExitCode::from(EXIT_FAILURE))
+        }
+    };
+}
+
+// day_input is where one day's input comes from: the --input it was given, or else its file in the input directory
+fn day_input(day: u32, input: Option<InputSource>, flag: Option<&Path>) -> Result<InputSource, ExitCode> {
+    if let Some(input) = input {
+        return Ok(input);
+    }
+    let path = inputs::input_path(&input_dir(flag)?, day);
+    if !path.exists() {
+        eprintln!("error: no input for day {day} at {} (use --input, or `aoc import {day} <path>` to add it)", path.display());
+        return Err(ExitCode::from(EXIT_FAILURE));
+    }
+    return Ok(InputSource::File(path));
+}
+
+fn read_input(source: &InputSource) -> io::Result<String> {
     let mut input = String::new();
-    file.read_to_string(&mut input)?;
+    match source {
+        InputSource::Stdin => io::stdin().read_to_string(&mut input)?,
+        InputSource::File(path) => fs::File::open(path)?.read_to_string(&mut input)?,
+    };
+    return Ok(input);
+}
+
+// parts is the parts to run: just the one if it was asked for, otherwise both
+fn parts(part: Option<Part>) -> Vec<Part> {
+    return match part {
+        Some(p) => vec![p],
+        None => Part::BOTH.to_vec(),
+    };
+}
+
+// Output prints the results of run and all in whichever format was asked for
+struct Output {
+    format: Format,
+    // JSON is written as a single array at the end, so the records are held on to until then
+    json_records: Vec<String>,
+}
+
+impl Output {
+    fn begin(format: Format) -> Output {
+        if format == Format::Csv {
+            println!("{}", report::CSV_HEADER);
+        }
+        return Output { format, json_records: Vec::new() };
+    }
+
+    // day is called before the records for each day
+    fn day(&self, registration: &Registration) {
+        if self.format == Format::Text {
+            println!("Day {}: {}", registration.day, registration.title);
+        }
+    }
+
+    fn record(&mut self, record: &Record) {
+        match self.format {
+            Format::Text => match &record.answer {
+                // a grid starts on its own line, so that its rows line up
+                Ok(answer @ Answer::Grid(_)) => println!("Part {}:\n{answer}", record.part),
+                Ok(answer) => println!("Part {}: {answer}", record.part),
+                Err(e) => eprintln!("error: {e}"),
+            },
+            Format::Json => self.json_records.push(record.to_json()),
+            Format::Csv => println!("{}", record.to_csv()),
+        }
+    }
+
+    fn end(self) {
+        if self.format == Format::Json {
+            println!("[");
+            println!("{}", self.json_records.join(",\n"));
+            println!("]");
+        }
+    }
+}
+
+// DayInput is a day's input for all, or why there isn't one
+enum DayInput {
+    Missing(PathBuf),
+    Unreadable(InputSource, io::Error),
+    Read(InputSource, String),
+}
+
+// solve_parts runs the requested parts of a day (both if part is None), writing each result to output as it goes
+// it returns whether everything succeeded
+fn solve_parts(registration: &'static Registration, part: Option<Part>, source: &InputSource, input: &str, output: &mut Output) -> bool {
+    let mut solved = parallel::solve_days(&[(registration, input)], &parts(part), 1);
+    return write_records(registration, solved.remove(0), source, input, output);
+}
 
-    // then actually execute it
-    println!("Part 1: {}", day_impl.part1(&input)); 
-    println!("Part 2: {}", day_impl.part2(&input));
+// write_records writes the results of a day to output, returning whether they all succeeded
+fn write_records(registration: &Registration, solved: DaySolved, source: &InputSource, input: &str, output: &mut Output) -> bool {
+    let input_hash = report::input_hash(input);
+    let mut ok = true;
+    for part in solved.parts {
+        ok &= part.answer.is_ok();
+        output.record(&Record {
+            day: registration.day,
+            part: part.part,
+            answer: part.answer,
+            elapsed: part.elapsed,
+            parse_elapsed: solved.parse,
+            input: source.to_string(),
+            input_hash: input_hash.clone(),
+        });
+    }
+    return ok;
+}
+
+fn run(day: u32, part: Option<Part>, input: Option<InputSource>, input_dir: Option<&Path>, format: Format) -> ExitCode {
+    let registration = match lookup(day) {
+        Ok(r) => r,
+        Err(e) => return usage_failure(e),
+    };
+    let source = match day_input(day, input, input_dir) {
+        Ok(s) => s,
+        Err(code) => return code,
+    };
+
+    aoc_2025::info!("reading input from {source}");
+    let input = match read_input(&source) {
+        Ok(i) => i,
+        Err(e) => {
+            eprintln!("error: couldn't read input {source}: {e}");
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+
+    let mut output = Output::begin(format);
+    let ok = solve_parts(registration, part, &source, &input, &mut output);
+    output.end();
+
+    if !ok {
+        return ExitCode::from(EXIT_FAILURE);
+    }
+    return ExitCode::SUCCESS;
+}
+
+// all solves every day with an input, on up to `jobs` threads
+// every input is read up front so that the days can be solved in any order, and then the results are written out in day order,
+// which means the output is the same however many jobs there are (apart from the timings)
+fn all(part: Option<Part>, input_dir: Option<&Path>, format: Format, jobs: usize) -> ExitCode {
+    let input_dir = match self::input_dir(input_dir) {
+        Ok(dir) => dir,
+        Err(code) => return code,
+    };
 
-    Ok(())
+    let days: Vec<(&'static Registration, DayInput)> = aoc_2025::registry().iter().map(|registration| {
+        let path = inputs::input_path(&input_dir, registration.day);
+        if !path.exists() {
+            return (registration, DayInput::Missing(path));
+        }
+        let source = InputSource::File(path);
+        return match read_input(&source) {
+            Ok(input) => (registration, DayInput::Read(source, input)),
+            Err(e) => (registration, DayInput::Unreadable(source, e)),
+        };
+    }).collect();
+
+    let to_solve: Vec<(&'static Registration, &str)> = days.iter()
+        .filter_map(|(registration, input)| match input {
+            DayInput::Read(_, input) => Some((*registration, input.as_str())),
+            _ => None,
+        })
+        .collect();
+
+    let start = Instant::now();
+    let mut solved = parallel::solve_days(&to_solve, &parts(part), jobs).into_iter();
+    let wall_clock = start.elapsed();
+
+    let mut ok = true;
+    let mut total = Duration::ZERO;
+    let mut output = Output::begin(format);
+    for (registration, input) in &days {
+        output.day(registration);
+        match input {
+            DayInput::Missing(path) => eprintln!("skipping day {}: no input at {}", registration.day, path.display()),
+            DayInput::Unreadable(source, e) => {
+                eprintln!("error: couldn't read input {source}: {e}");
+                ok = false;
+            }
+            DayInput::Read(source, input) => {
+                let day_solved = solved.next().expect("every day with an input was solved");
+
+                let elapsed = day_solved.parse.unwrap_or_default() + day_solved.parts.iter().map(|s| s.elapsed).sum::<Duration>();
+                total += elapsed;
+                match day_solved.parse {
+                    Some(parse) => eprintln!("day {} took {elapsed:.2?} ({parse:.2?} of that parsing)", registration.day),
+                    None => eprintln!("day {} took {elapsed:.2?}", registration.day),
+                }
+                ok &= write_records(registration, day_solved, source, input, &mut output);
+            }
+        }
+    }
+    output.end();
+
+    // with more than one job the parts overlap, so the wall clock time is less than the total of the parts
+    eprintln!("all took {wall_clock:.2?} with --jobs {jobs} ({total:.2?} of solving in total)");
+
+    if !ok {
+        return ExitCode::from(EXIT_FAILURE);
+    }
+    return ExitCode::SUCCESS;
+}
+
+fn list() -> ExitCode {
+    for registration in aoc_2025::registry() {
+        println!("{:>2}  {}", registration.day, registration.title);
+    }
+    return ExitCode::SUCCESS;
+}
+
+fn format_duration(d: Option<Duration>) -> String {
+    return match d {
+        Some(d) => format!("{d:.2?}"),
+        None => "-".to_string(),
+    };
+}
+
+fn print_bench_row(b: &PartBench) {
+    println!("{:>3} {:>4} {:>5} {:>10} {:>10} {:>10} {:>10} {:>10}",
+             b.day, b.part.number(), b.total.runs,
+             format_duration(Some(b.total.min)), format_duration(Some(b.total.median)), format_duration(Some(b.total.max)),
+             format_duration(b.parse.map(|p| p.median)), format_duration(b.solve.map(|s| s.median)));
+}
+
+fn bench(target: &BenchTarget, part: Option<Part>, input_dir: Option<&Path>, iterations: usize) -> ExitCode {
+    let days = match target {
+        BenchTarget::Day(day, input) => {
+            let registration = match lookup(*day) {
+                Ok(r) => r,
+                Err(e) => return usage_failure(e),
+            };
+            match day_input(*day, input.clone(), input_dir) {
+                Ok(source) => vec![(registration, source)],
+                Err(code) => return code,
+            }
+        }
+        BenchTarget::All => {
+            let input_dir = match self::input_dir(input_dir) {
+                Ok(dir) => dir,
+                Err(code) => return code,
+            };
+            let mut days = Vec::new();
+            for registration in aoc_2025::registry() {
+                let path = inputs::input_path(&input_dir, registration.day);
+                if path.exists() {
+                    days.push((registration, InputSource::File(path)));
+                } else {
+                    eprintln!("skipping day {}: no input at {}", registration.day, path.display());
+                }
+            }
+            days
+        }
+    };
+
+    // parse and solve are medians, and are only there for puzzles that are a Solution (so parse their input separately)
+    println!("{:>3} {:>4} {:>5} {:>10} {:>10} {:>10} {:>10} {:>10}", "day", "part", "runs", "min", "median", "max", "parse", "solve");
+    let mut ok = true;
+    for (registration, source) in days {
+        let input = match read_input(&source) {
+            Ok(i) => i,
+            Err(e) => {
+                eprintln!("error: couldn't read input {source}: {e}");
+                ok = false;
+                continue;
+            }
+        };
+
+        for part in parts(part) {
+            match bench::bench_part(registration, part, &input, iterations) {
+                Ok(b) => print_bench_row(&b),
+                Err(e) => {
+                    eprintln!("error: {e}");
+                    ok = false;
+                    break;
+                }
+            }
+        }
+    }
+
+    if !ok {
+        return ExitCode::from(EXIT_FAILURE);
+    }
+    return ExitCode::SUCCESS;
+}
+
+fn verify(input_dir: Option<&Path>, answers_dir: &Path) -> ExitCode {
+    let input_dir = match self::input_dir(input_dir) {
+        Ok(dir) => dir,
+        Err(code) => return code,
+    };
+
+    println!("{:>3} {:>4}  {:<7} detail", "day", "part", "status");
+    let mut regressions = 0;
+    for registration in aoc_2025::registry() {
+        let path = inputs::input_path(&input_dir, registration.day);
+        if !path.exists() {
+            println!("{:>3} {:>4}  {:<7} no input at {}", registration.day, "-", "skipped", path.display());
+            continue;
+        }
+
+        // a broken answers file or unreadable input is a failure for the whole day, since we can't check anything
+        let loaded = answers::load(answers_dir, registration.day)
+            .and_then(|answers| Ok((answers.unwrap_or_default(), read_input(&InputSource::File(path.clone()))?)));
+        let (recorded, input): (Answers, String) = match loaded {
+            Ok(l) => l,
+            Err(e) => {
+                println!("{:>3} {:>4}  {:<7} {e}", registration.day, "-", "error");
+                regressions += 1;
+                continue;
+            }
+        };
+
+        for part in Part::BOTH {
+            let outcome = answers::verify(registration, part, &input, &recorded);
+            let (status, detail) = match &outcome {
+                Outcome::Pass => ("pass", String::new()),
+                // when they look the same it's the type that's different, so say what the types are
+                Outcome::Fail { expected, actual } if expected.to_string() == actual.to_string() => {
+                    ("FAIL", format!("expected {expected} ({}), got {actual} ({})", expected.kind(), actual.kind()))
+                },
+                Outcome::Fail { expected, actual } => ("FAIL", format!("expected {expected}, got {actual}")),
+                Outcome::Error(e) => ("ERROR", e.to_string()),
+                Outcome::Missing { actual } => ("missing", format!("got {actual}, record it in {}", answers::answers_path(answers_dir, registration.day).display())),
+            };
+            println!("{:>3} {:>4}  {:<7} {detail}", registration.day, part.number(), status);
+            if outcome.is_regression() {
+                regressions += 1;
+            }
+        }
+    }
+
+    if regressions > 0 {
+        eprintln!("{regressions} regression(s)");
+        return ExitCode::from(EXIT_FAILURE);
+    }
+    return ExitCode::SUCCESS;
+}
+
+fn import(day: u32, from: &InputSource, input_dir: Option<&Path>, force: bool) -> ExitCode {
+    // importing an input for a day that doesn't exist yet is fine, since that's how a new day starts
+    let input_dir = match self::input_dir(input_dir) {
+        Ok(dir) => dir,
+        Err(code) => return code,
+    };
+    let input = match read_input(from) {
+        Ok(i) => i,
+        Err(e) => {
+            eprintln!("error: couldn't read input {from}: {e}");
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+
+    return match inputs::import(&input_dir, day, &input, force) {
+        Ok(path) => {
+            println!("imported {from} as {}", path.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            eprintln!("error: {e} (use --force to overwrite it)");
+            ExitCode::from(EXIT_FAILURE)
+        }
+        Err(e) => {
+            eprintln!("error: couldn't import {from}: {e}");
+            ExitCode::from(EXIT_FAILURE)
+        }
+    };
+}
+
+fn new(day: u32, title: &str) -> ExitCode {
+    if let Some(registration) = aoc_2025::lookup(day) {
+        return usage_failure(cli::UsageError(format!("day {day} already exists ({})", registration.title)));
+    }
+
+    // this is the crate the binary was built from, which is the one the new day should be added to
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    return match scaffold::scaffold(root, day, title) {
+        Ok(paths) => {
+            for path in paths {
+                println!("wrote {}", path.display());
+            }
+            println!("day {day} will be registered from the next build; paste the example into examples/day{day}.txt to test against it");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: couldn't create day {day}: {e}");
+            ExitCode::from(EXIT_FAILURE)
+        }
+    };
+}
+
+// trace writes out each step of a day's walk through its input, which only day 1 has so far
+fn trace(day: u32, input: Option<InputSource>, input_dir: Option<&Path>, format: Format) -> ExitCode {
+    if let Err(e) = lookup(day) {
+        return usage_failure(e);
+    }
+    if day != 1 {
+        return usage_failure(cli::UsageError(format!("day {day} can't be traced (only day 1 can)")));
+    }
+    let source = match day_input(day, input, input_dir) {
+        Ok(s) => s,
+        Err(code) => return code,
+    };
+    let input = match read_input(&source) {
+        Ok(i) => i,
+        Err(e) => {
+            eprintln!("error: couldn't read input {source}: {e}");
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+
+    let steps = match Day1.trace(&input) {
+        Ok(steps) => steps,
+        Err(e) => {
+            eprintln!("error: {}", e.in_day(day));
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+    match format {
+        Format::Json => {
+            println!("[");
+            println!("{}", steps.iter().map(|step| step.to_json()).collect::<Vec<_>>().join(",\n"));
+            println!("]");
+        }
+        _ => {
+            println!("{}", Day1::TRACE_CSV_HEADER);
+            for step in &steps {
+                println!("{}", step.to_csv());
+            }
+        }
+    }
+    return ExitCode::SUCCESS;
 }
@@ -0,0 +1,95 @@
+use crate::PuzzleError;
+
+// minitoml reads the (very small) subset of TOML that our config and answers files need:
+// comments, blank lines, and `key = value` where the value is either a basic "string" or a bare integer
+// it's nowhere near the full language (no tables, arrays, floats...), but it means we don't need a dependency for it
+
+// Value is a value as it was written: a quoted string, or a bare integer (kept as its digits, since it could be any size)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    String(String),
+    Integer(String),
+}
+
+impl Value {
+    // into_string is the value as text, for keys that don't care which it was
+    pub fn into_string(self) -> String {
+        return match self {
+            Value::String(s) | Value::Integer(s) => s,
+        };
+    }
+}
+
+// parse returns the (1-indexed) line number, key and value of every key/value pair, in order
+pub fn parse(contents: &str) -> Result<Vec<(usize, String, Value)>, PuzzleError> {
+    let mut pairs = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line_number = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (key, value) = line.split_once('=')
+            .ok_or_else(|| PuzzleError::at(line_number, 1, format!("expected `key = value`, got {line:?}")))?;
+        let value = parse_value(value.trim())
+            .ok_or_else(|| PuzzleError::at(line_number, 1, format!("invalid value {:?}", value.trim())))?;
+        pairs.push((line_number, key.trim().to_string(), value));
+    }
+    return Ok(pairs);
+}
+
+// parse_value parses a quoted string (with the usual backslash escapes) or a bare integer, ignoring any trailing comment
+fn parse_value(value: &str) -> Option<Value> {
+    let Some(quoted) = value.strip_prefix('"') else {
+        let bare = value.split('#').next()?.trim();
+        let digits = bare.strip_prefix('-').unwrap_or(bare);
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit() || c == '_') {
+            return None;
+        }
+        return Some(Value::Integer(bare.replace('_', "")));
+    };
+
+    let mut result = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                // only a comment is allowed after the closing quote
+                let rest = chars.as_str().trim();
+                if rest.is_empty() || rest.starts_with('#') {
+                    return Some(Value::String(result));
+                }
+                return None;
+            }
+            '\\' => result.push(match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                '"' => '"',
+                '\\' => '\\',
+                _ => return None,
+            }),
+            c => result.push(c),
+        }
+    }
+
+    // we never found the closing quote
+    return None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_escapes_in_strings() {
+        let pairs = parse("path = \"a \\\"b\\\"\\nc\"").unwrap();
+        assert_eq!(pairs, vec![(1, "path".to_string(), Value::String("a \"b\"\nc".to_string()))]);
+    }
+
+    #[test]
+    fn skips_comments_and_blank_lines() {
+        let pairs = parse("# a comment\n\n  x = 1 # trailing\ny = \"#not a comment\"\n").unwrap();
+        assert_eq!(pairs, vec![(3, "x".to_string(), Value::Integer("1".to_string())), (4, "y".to_string(), Value::String("#not a comment".to_string()))]);
+    }
+}
@@ -0,0 +1,142 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use crate::{Answer, Part, PuzzleError, Registration};
+use crate::bench::time;
+
+// every day (and each part of a day) is independent of the others, so they can be solved on as many threads as there are to spare
+// this is a very small thread pool: each worker keeps taking the next job that nobody has started yet until there are none left
+
+// Solved is the outcome of solving one part of a day, and how long it took
+#[derive(Clone, Debug)]
+pub struct Solved {
+    pub part: Part,
+    pub answer: Result<Answer, PuzzleError>,
+    pub elapsed: Duration,
+}
+
+// DaySolved is the outcome of solving the parts of one day
+#[derive(Clone, Debug)]
+pub struct DaySolved {
+    // how long it took to parse the input, for a Solution (which parses it once for both parts)
+    pub parse: Option<Duration>,
+    pub parts: Vec<Solved>,
+}
+
+// map runs f on every item using up to `threads` threads, and returns the results in the same order as the items
+// with one thread (or one item) it doesn't start any threads at all, so that's the same as running f on them one after another
+fn map<T: Sync, R: Send>(items: &[T], threads: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let threads = threads.clamp(1, items.len().max(1));
+    if threads == 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<Option<R>> = (0..items.len()).map(|_| None).collect();
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads).map(|_| scope.spawn(|| {
+            let mut done = Vec::new();
+            loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= items.len() {
+                    return done;
+                }
+                done.push((i, f(&items[i])));
+            }
+        })).collect();
+
+        for worker in workers {
+            // the solvers return errors rather than panicking, so a panic here is a bug worth seeing in full
+            let done = worker.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+            for (i, r) in done {
+                results[i] = Some(r);
+            }
+        }
+    });
+
+    return results.into_iter().map(|r| r.expect("every item is taken by exactly one worker")).collect();
+}
+
+// solve_days solves the given parts of each day (with its input), using up to `threads` threads, and returns the results in day order
+// it goes in two steps: first every day's input is parsed, and then every part of every day is solved from its parsed input,
+// so a Solution only parses its input once however many of its parts are run
+pub fn solve_days(days: &[(&'static Registration, &str)], parts: &[Part], threads: usize) -> Vec<DaySolved> {
+    let parsed = map(days, threads, |(registration, input)| {
+        let (parsed, elapsed) = time(|| registration.parse(input));
+        if parsed.as_ref().is_ok_and(|p| p.parsed_once()) {
+            crate::info!("day {} took {elapsed:.2?} to parse", registration.day);
+        }
+        return (parsed, elapsed);
+    });
+
+    // only the days whose inputs parsed have anything to solve
+    let jobs: Vec<(usize, Part)> = parsed.iter()
+        .enumerate()
+        .filter(|(_, (p, _))| p.is_ok())
+        .flat_map(|(i, _)| parts.iter().map(move |&part| (i, part)))
+        .collect();
+    let solved = map(&jobs, threads, |&(i, part)| {
+        let registration = days[i].0;
+        let input = parsed[i].0.as_ref().expect("only parsed inputs have jobs");
+        crate::info!("running day {} part {part}", registration.day);
+        let (answer, elapsed) = time(|| registration.solve_parsed(input.as_ref(), part));
+        crate::info!("day {} part {part} took {elapsed:.2?}", registration.day);
+        return Solved { part, answer, elapsed };
+    });
+
+    let mut solved = solved.into_iter();
+    return days.iter().zip(&parsed).map(|((registration, _), (input, elapsed))| match input {
+        Ok(input) => DaySolved {
+            parse: input.parsed_once().then_some(*elapsed),
+            parts: solved.by_ref().take(parts.len()).collect(),
+        },
+        // if the input didn't parse then neither part can be solved, so that's the error for both of them
+        Err(e) => DaySolved {
+            parse: Some(*elapsed),
+            parts: parts.iter().map(|&part| Solved {
+                part,
+                answer: Err(e.clone().in_part(registration.day, part)),
+                elapsed: Duration::ZERO,
+            }).collect(),
+        },
+    }).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(solved: Vec<DaySolved>) -> Vec<Result<Answer, PuzzleError>> {
+        return solved.into_iter().flat_map(|d| d.parts).map(|s| s.answer).collect();
+    }
+
+    #[test]
+    fn parallel_results_match_sequential_ones() {
+        let day0 = crate::lookup(0).unwrap();
+        let day9 = crate::lookup(9).unwrap();
+        let days = [(day0, "abc"), (day9, "7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3\n"), (day0, ""), (day9, "1,x\n")];
+
+        let sequential = answers(solve_days(&days, &Part::BOTH, 1));
+        assert_eq!(sequential[..6], [Ok("abc".into()), Ok("cba".into()), Ok(50.into()), Ok(24.into()), Ok("".into()), Ok("".into())]);
+        // day 9's input doesn't parse, so that's an error in both parts
+        assert_eq!(sequential[6].as_ref().unwrap_err().part, Some(Part::One));
+        assert_eq!(sequential[7].as_ref().unwrap_err().part, Some(Part::Two));
+
+        assert_eq!(answers(solve_days(&days, &Part::BOTH, 4)), sequential);
+        assert_eq!(answers(solve_days(&days, &[Part::Two], 100))[..], [sequential[1].clone(), sequential[3].clone(), sequential[5].clone(), sequential[7].clone()]);
+    }
+
+    #[test]
+    fn only_solutions_report_parse_times() {
+        let days = [(crate::lookup(0).unwrap(), "abc"), (crate::lookup(9).unwrap(), "1,1\n2,2\n")];
+        let solved = solve_days(&days, &Part::BOTH, 2);
+        assert!(solved[0].parse.is_none());
+        assert!(solved[1].parse.is_some());
+    }
+
+    #[test]
+    fn no_days() {
+        assert!(solve_days(&[], &Part::BOTH, 8).is_empty());
+    }
+}
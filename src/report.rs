@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+use crate::{Answer, Part, PuzzleError};
+
+// Record is the machine-readable result of running one part of one day, for anything scraping the output (e.g. a dashboard)
+#[derive(Clone, Debug)]
+pub struct Record {
+    pub day: u32,
+    pub part: Part,
+    pub answer: Result<Answer, PuzzleError>,
+    // elapsed is the time to solve the part; for a Solution, that's from the input once it's been parsed,
+    // and parse_elapsed is how long that took (which is the same for both parts, since it's only parsed once)
+    pub elapsed: Duration,
+    pub parse_elapsed: Option<Duration>,
+    // where the input came from, e.g. a path or <stdin>
+    pub input: String,
+    // see input_hash
+    pub input_hash: String,
+}
+
+pub const CSV_HEADER: &str = "day,part,answer,answer_type,elapsed_ns,parse_ns,input,input_hash,error,error_line,error_column";
+
+impl Record {
+    // to_json writes the record as a single line of JSON, e.g.
+    // {"day":1,"part":1,"answer":3,"answer_type":"integer","elapsed_ns":1234,"parse_ns":567,"input":"inputs/day01.txt","input_hash":"fnv1a64:...","error":null}
+    // the answer is written as its type (see json_answer), and answer_type says which type that is
+    pub fn to_json(&self) -> String {
+        let (answer, answer_type, error) = match &self.answer {
+            Ok(a) => (json_answer(a), json_string(a.kind()), "null".to_string()),
+            Err(e) => ("null".to_string(), "null".to_string(), format!(
+                "{{\"message\":{},\"line\":{},\"column\":{},\"description\":{}}}",
+                json_string(&e.message), json_option(e.line), json_option(e.column), json_string(&e.to_string()),
+            )),
+        };
+        return format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"answer_type\":{},\"elapsed_ns\":{},\"parse_ns\":{},\"input\":{},\"input_hash\":{},\"error\":{}}}",
+            self.day, self.part.number(), answer, answer_type, self.elapsed.as_nanos(), json_option(self.parse_elapsed.map(|p| p.as_nanos())),
+            json_string(&self.input), json_string(&self.input_hash), error,
+        );
+    }
+
+    // to_csv writes the record as a single CSV row, matching CSV_HEADER
+    pub fn to_csv(&self) -> String {
+        let (answer, answer_type, error, line, column) = match &self.answer {
+            Ok(a) => (a.to_string(), a.kind(), "", String::new(), String::new()),
+            Err(e) => (String::new(), "", e.message.as_str(), csv_option(e.line), csv_option(e.column)),
+        };
+        return [
+            self.day.to_string(),
+            self.part.number().to_string(),
+            csv_field(&answer),
+            answer_type.to_string(),
+            self.elapsed.as_nanos().to_string(),
+            csv_option(self.parse_elapsed.map(|p| p.as_nanos())),
+            csv_field(&self.input),
+            csv_field(&self.input_hash),
+            csv_field(error),
+            line,
+            column,
+        ].join(",");
+    }
+}
+
+// input_hash identifies an input by its contents, so that e.g. a dashboard can tell whether two runs were on the same input
+// it's 64-bit FNV-1a rather than std's DefaultHasher, because that isn't guaranteed to be stable between Rust versions
+pub fn input_hash(input: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in input.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    return format!("fnv1a64:{hash:016x}");
+}
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    return escaped;
+}
+
+// json_answer writes numbers as JSON numbers (at full precision, even past what a double can hold), text as a string,
+// and a grid as an array of its rows
+fn json_answer(answer: &Answer) -> String {
+    return match answer {
+        Answer::Integer(v) => v.to_string(),
+        Answer::BigInteger(v) => v.to_string(),
+        Answer::Text(s) => json_string(s),
+        Answer::Grid(rows) => format!("[{}]", rows.iter().map(|row| json_string(row)).collect::<Vec<_>>().join(",")),
+    };
+}
+
+fn json_option(v: Option<impl ToString>) -> String {
+    return match v {
+        Some(v) => v.to_string(),
+        None => "null".to_string(),
+    };
+}
+
+// csv_field quotes a field if it needs it (RFC 4180 style: wrap in quotes, and double any quotes inside)
+pub(crate) fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        return format!("\"{}\"", s.replace('"', "\"\""));
+    }
+    return s.to_string();
+}
+
+fn csv_option(v: Option<impl ToString>) -> String {
+    return v.map(|v| v.to_string()).unwrap_or_default();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(answer: Result<Answer, PuzzleError>) -> Record {
+        return Record {
+            day: 9,
+            part: Part::Two,
+            answer,
+            elapsed: Duration::from_micros(15),
+            parse_elapsed: None,
+            input: "inputs/day09.txt".to_string(),
+            input_hash: input_hash("7,1\n"),
+        };
+    }
+
+    #[test]
+    fn input_hash_matches_reference_values() {
+        // the test vectors from the FNV reference
+        assert_eq!(input_hash(""), "fnv1a64:cbf29ce484222325");
+        assert_eq!(input_hash("a"), "fnv1a64:af63dc4c8601ec8c");
+        assert_eq!(input_hash("foobar"), "fnv1a64:85944171f73967e8");
+    }
+
+    #[test]
+    fn json_for_an_answer() {
+        let json = record(Ok(Answer::from(24))).to_json();
+        assert_eq!(json, format!(
+            "{{\"day\":9,\"part\":2,\"answer\":24,\"answer_type\":\"integer\",\"elapsed_ns\":15000,\"parse_ns\":null,\"input\":\"inputs/day09.txt\",\"input_hash\":\"{}\",\"error\":null}}",
+            input_hash("7,1\n"),
+        ));
+    }
+
+    #[test]
+    fn json_for_an_error_is_escaped() {
+        let json = record(Err(PuzzleError::at(2, 3, "invalid coordinate \"x\"").in_part(9, Part::Two))).to_json();
+        assert!(json.contains("\"answer\":null,\"answer_type\":null,"));
+        assert!(json.contains("\"error\":{\"message\":\"invalid coordinate \\\"x\\\"\",\"line\":2,\"column\":3,"));
+    }
+
+    #[test]
+    fn json_keeps_the_answers_type() {
+        assert!(record(Ok(Answer::from(u64::MAX))).to_json().contains("\"answer\":18446744073709551615,\"answer_type\":\"big_integer\","));
+        assert!(record(Ok(Answer::from("24"))).to_json().contains("\"answer\":\"24\",\"answer_type\":\"text\","));
+        let grid = Answer::Grid(vec!["#.".to_string(), ".#".to_string()]);
+        assert!(record(Ok(grid)).to_json().contains("\"answer\":[\"#.\",\".#\"],\"answer_type\":\"grid\","));
+    }
+
+    #[test]
+    fn csv_quotes_fields_that_need_it() {
+        let csv = record(Err(PuzzleError::new("bad \"range\", really"))).to_csv();
+        assert_eq!(csv, format!("9,2,,,15000,,inputs/day09.txt,{},\"bad \"\"range\"\", really\",,", input_hash("7,1\n")));
+    }
+
+    #[test]
+    fn csv_matches_header() {
+        let csv = Record { parse_elapsed: Some(Duration::from_nanos(700)), ..record(Ok(Answer::from(24))) }.to_csv();
+        assert_eq!(csv.split(',').count(), CSV_HEADER.split(',').count());
+        assert!(csv.starts_with("9,2,24,integer,15000,700,"));
+    }
+}
@@ -0,0 +1,138 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// this is what `aoc new <day>` uses to start a new day: it writes src/aoc/dayN.rs from a template, adds the day to the days! list
+// in src/aoc.rs, and puts an empty example in examples/ ready for the puzzle's worked example to be pasted in
+// it edits the source tree the binary was built from, so the new day is picked up by the next cargo build
+
+// day_source is the starting point for src/aoc/dayN.rs
+pub fn day_source(day: u32, title: &str) -> String {
+    return format!(r#"use crate::{{Answer, Puzzle, PuzzleError}};
+
+// Day{day} implements day {day} of AoC 2025, as uploaded at https://adventofcode.com/2025/day/{day}.
+pub struct Day{day};
+register!({day}, {title:?}, Day{day});
+
+impl Puzzle for Day{day} {{
+    fn part1(&self, _input: &str) -> Result<Answer, PuzzleError> {{
+        return Err(PuzzleError::new("part 1 isn't solved yet"));
+    }}
+    fn part2(&self, _input: &str) -> Result<Answer, PuzzleError> {{
+        return Err(PuzzleError::new("part 2 isn't solved yet"));
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    // the worked example is checked by tests/examples.rs (from examples/day{day}.txt), so these are for the parser and helpers
+    #[test]
+    fn empty_input_is_an_error() {{
+        assert!(Day{day}.part1("").is_err());
+        assert!(Day{day}.part2("").is_err());
+    }}
+}}
+"#);
+}
+
+// example_expected is the placeholder examples/dayN.expected, which checks nothing until the answers are filled in
+pub fn example_expected(day: u32) -> String {
+    return format!("# the answers to the worked example in examples/day{day}.txt, once it's been pasted in from the puzzle, e.g.\n# part1: 42\n# part2: 1337\n");
+}
+
+// add_to_days_list adds dayN::DayN to the days! list in the contents of src/aoc.rs, keeping the list in day order
+pub fn add_to_days_list(aoc_rs: &str, day: u32) -> Result<String, String> {
+    let start = match aoc_rs.find("days! {\n") {
+        Some(i) => i + "days! {\n".len(),
+        None => return Err("couldn't find the days! list".to_string()),
+    };
+    let end = match aoc_rs[start..].find("\n}") {
+        Some(i) => start + i + 1,
+        None => return Err("couldn't find the end of the days! list".to_string()),
+    };
+
+    let mut days = Vec::new();
+    for entry in aoc_rs[start..end].lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        let listed = entry.strip_prefix("day")
+            .and_then(|rest| rest.split_once("::"))
+            .and_then(|(n, _)| n.parse::<u32>().ok());
+        match listed {
+            Some(d) if d == day => return Err(format!("day {day} is already in the days! list")),
+            Some(d) => days.push((d, entry.to_string())),
+            None => return Err(format!("couldn't understand {entry:?} in the days! list")),
+        }
+    }
+    days.push((day, format!("day{day}::Day{day},")));
+    days.sort_by_key(|(d, _)| *d);
+
+    let mut updated = aoc_rs[..start].to_string();
+    for (_, entry) in days {
+        updated.push_str(&format!("    {entry}\n"));
+    }
+    updated.push_str(&aoc_rs[end..]);
+    return Ok(updated);
+}
+
+// scaffold creates everything for a new day under root (the crate's directory), returning the paths it wrote
+// it won't overwrite anything, so it checks everything up front rather than leaving a half-made day behind
+pub fn scaffold(root: &Path, day: u32, title: &str) -> io::Result<Vec<PathBuf>> {
+    let source = root.join("src").join("aoc").join(format!("day{day}.rs"));
+    let example = root.join("examples").join(format!("day{day}.txt"));
+    let expected = example.with_extension("expected");
+    let aoc_rs = root.join("src").join("aoc.rs");
+
+    for path in [&source, &example, &expected] {
+        if path.exists() {
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists", path.display())));
+        }
+    }
+    let days_list = add_to_days_list(&fs::read_to_string(&aoc_rs)?, day)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {e}", aoc_rs.display())))?;
+
+    fs::write(&source, day_source(day, title))?;
+    fs::write(&example, "")?;
+    fs::write(&expected, example_expected(day))?;
+    fs::write(&aoc_rs, days_list)?;
+    return Ok(vec![source, example, expected, aoc_rs]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AOC_RS: &str = "use crate::Puzzle;\n\ndays! {\n    day0::Day0,\n    day1::Day1,\n    day9::Day9,\n}\n\npub fn registry() {}\n";
+
+    #[test]
+    fn adds_days_in_order() {
+        let updated = add_to_days_list(AOC_RS, 3).unwrap();
+        assert_eq!(updated, "use crate::Puzzle;\n\ndays! {\n    day0::Day0,\n    day1::Day1,\n    day3::Day3,\n    day9::Day9,\n}\n\npub fn registry() {}\n");
+
+        let updated = add_to_days_list(&updated, 12).unwrap();
+        assert!(updated.contains("    day9::Day9,\n    day12::Day12,\n}\n"));
+    }
+
+    #[test]
+    fn refuses_a_day_thats_already_listed() {
+        assert!(add_to_days_list(AOC_RS, 9).is_err());
+        assert!(add_to_days_list("fn main() {}\n", 1).is_err());
+    }
+
+    #[test]
+    fn the_real_days_list_can_be_extended() {
+        let aoc_rs = include_str!("aoc.rs");
+        let updated = add_to_days_list(aoc_rs, 99).unwrap();
+        assert_eq!(updated.len(), aoc_rs.len() + "    day99::Day99,\n".len());
+    }
+
+    #[test]
+    fn template_registers_the_day() {
+        let source = day_source(10, "Some \"Quoted\" Title");
+        assert!(source.contains("pub struct Day10;\nregister!(10, \"Some \\\"Quoted\\\" Title\", Day10);\n"));
+        assert!(source.starts_with("use crate::{Answer, Puzzle, PuzzleError};\n\n// Day10 implements day 10 of AoC 2025, as uploaded at https://adventofcode.com/2025/day/10.\n"));
+        assert!(source.contains("impl Puzzle for Day10 {"));
+        assert!(source.contains("\n#[cfg(test)]\nmod tests {\n    use super::*;\n"));
+        assert!(source.contains("fn empty_input_is_an_error() {\n        assert!(Day10.part1(\"\").is_err());"));
+    }
+}
@@ -0,0 +1,89 @@
+// runs every registered day against the worked examples in examples/
+// each example is a dayN.txt input (or dayN-<name>.txt, for days with more than one) next to a .expected file like:
+//
+//     # comments are fine
+//     part1: 3
+//     part2: 6
+//
+// a part without an expected answer is skipped, for examples that only apply to one part
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_2025::{Answer, Part};
+
+struct Example {
+    day: u32,
+    input: PathBuf,
+    expected: Vec<(Part, String)>,
+}
+
+// day_of parses the day number out of an example's name, e.g. day12-small -> 12
+fn day_of(stem: &str) -> Option<u32> {
+    let digits = stem.strip_prefix("day")?;
+    let digits = digits.split('-').next()?;
+    return digits.parse::<u32>().ok();
+}
+
+fn parse_expected(path: &Path) -> Vec<(Part, String)> {
+    let contents = fs::read_to_string(path).unwrap();
+    let mut expected = Vec::new();
+    for line in contents.lines().map(|l| l.trim()) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line.split_once(':')
+            .unwrap_or_else(|| panic!("{}: expected `partN: answer`, got {line:?}", path.display()));
+        let part = match key.trim() {
+            "part1" => Part::One,
+            "part2" => Part::Two,
+            other => panic!("{}: unknown part {other:?}", path.display()),
+        };
+        expected.push((part, value.trim().to_string()));
+    }
+    return expected;
+}
+
+fn examples() -> Vec<Example> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples");
+    let mut examples = Vec::new();
+    for entry in fs::read_dir(&dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|ext| ext != "expected") {
+            continue;
+        }
+        let stem = path.file_stem().unwrap().to_str().unwrap();
+        let day = day_of(stem).unwrap_or_else(|| panic!("{} isn't named dayN.expected", path.display()));
+        examples.push(Example {
+            day,
+            input: path.with_extension("txt"),
+            expected: parse_expected(&path),
+        });
+    }
+    examples.sort_by(|a, b| a.input.cmp(&b.input));
+    return examples;
+}
+
+#[test]
+fn examples_match_expected_answers() {
+    let examples = examples();
+    assert!(!examples.is_empty(), "no examples found");
+
+    let mut failures = Vec::new();
+    for example in examples {
+        let registration = aoc_2025::lookup(example.day)
+            .unwrap_or_else(|| panic!("{} is for day {}, which isn't registered", example.input.display(), example.day));
+        let input = fs::read_to_string(&example.input)
+            .unwrap_or_else(|e| panic!("couldn't read {}: {e}", example.input.display()));
+
+        for (part, expected) in example.expected {
+            match registration.solve(part, &input) {
+                // the expected answers are compared by type, so e.g. a number has to come back as a number
+                Ok(answer) if answer == Answer::parse(&expected) => {},
+                Ok(answer) => failures.push(format!("{} part {part}: expected {expected}, got {answer}", example.input.display())),
+                Err(e) => failures.push(format!("{}: {e}", example.input.display())),
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "examples failed:\n{}", failures.join("\n"));
+}